@@ -1,8 +1,10 @@
 use serde_yaml::{Mapping, Value};
-use std::{str::FromStr, collections::HashMap};
+use std::str::FromStr;
 use thirtyfour::By;
 
-use crate::variables::resolve_variables;
+use crate::executor::WebDriverSession;
+
+const FRAME: &str = "frame";
 
 type ElementValue<'a> = (&'a Value, &'a Value);
 type ElementStr<'a> = (&'a str, &'a str);
@@ -11,6 +13,8 @@ type ElementStr<'a> = (&'a str, &'a str);
 pub struct Element {
     pub element_type: ElementType,
     pub value: String,
+    /// The iframe holding the element, `frame: { id: 'payment' }`.
+    pub frame: Option<Box<Element>>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -36,13 +40,36 @@ impl FromStr for ElementType {
 impl Element {
     pub fn new(task: &Mapping) -> Result<Self, String> {
         let element = Self::get_element(task)?;
+        Self::with_frame(&element)
+    }
+
+    /// Builds the element from the locator itself, `{ id: 'name' }`. Only
+    /// the task element is looked up in its frame, so `frame` is refused.
+    pub fn from_locator(element: &Mapping) -> Result<Self, String> {
+        if element.contains_key(FRAME) {
+            return Err("Element: frame is only supported on the task element".to_string());
+        }
+        Self::with_frame(element)
+    }
 
-        if element.len() == 1 {
-            let element: ElementValue = element.iter().last().unwrap();
+    /// Like `from_locator` for the task element, the executor enters its
+    /// `frame` before the task runs.
+    pub fn with_frame(element: &Mapping) -> Result<Self, String> {
+        let frame = match element.get(FRAME) {
+            None => None,
+            Some(frame) => match frame.as_mapping() {
+                Some(frame) => Some(Box::new(Self::with_frame(frame)?)),
+                None => return Err("Element: frame is not a map".to_string()),
+            },
+        };
+
+        let mut locator = element.iter().filter(|(key, _)| key.as_str() != Some(FRAME));
+        if let (Some(element), None) = (locator.next(), locator.next()) {
             let (element_key, element_value) = Self::get_element_value(element)?;
             return Ok(Element {
                 element_type: ElementType::from_str(element_key)?,
                 value: String::from(element_value),
+                frame,
             });
         }
 
@@ -90,22 +117,79 @@ impl Element {
         }
     }
 
-    pub fn find_by_resolve(element: &Element, vars: &HashMap<String, String>) -> By {
-        let value = resolve_variables(&element.value, vars);
-        
-        match element.element_type {
+    pub fn find_by_resolve(
+        element: &Element,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<By, String> {
+        let value = web_driver_session.resolve(&element.value)?;
+
+        Ok(match element.element_type {
             ElementType::CLASSNAME => By::ClassName(&value),
             ElementType::ID => By::Id(&value),
             ElementType::XPATH => By::XPath(&value),
+        })
+    }
+
+    /// Enters the iframes holding `element`, outermost first, and returns
+    /// how many were entered.
+    pub async fn enter_frames(
+        element: &Element,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<usize, String> {
+        let mut frames: Vec<&Element> = Vec::new();
+        let mut frame = element.frame.as_deref();
+        while let Some(parent) = frame {
+            frames.push(parent);
+            frame = parent.frame.as_deref();
         }
+
+        for (depth, frame) in frames.iter().rev().enumerate() {
+            let by = Self::find_by_resolve(frame, web_driver_session)?;
+            let entered = match web_driver_session.driver.find(by).await {
+                Ok(frame) => frame.enter_frame().await.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = entered {
+                Self::leave_frames(depth, web_driver_session).await?;
+                return Err(format!("Unable to switch to frame [{}]: {}", frame.value, e));
+            }
+        }
+
+        Ok(frames.len())
+    }
+
+    /// Goes back up `frames` levels, undoing `enter_frames`.
+    pub async fn leave_frames(
+        frames: usize,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<(), String> {
+        for _ in 0..frames {
+            if let Err(e) = web_driver_session.driver.enter_parent_frame().await {
+                return Err(format!("Unable to switch to parent frame: {}", e));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `value` as an XPath string. XPath has no escapes, so a value with both
+/// quotes is joined with `concat()`.
+pub fn xpath_literal(value: &str) -> String {
+    if !value.contains('\'') {
+        return format!("'{}'", value);
+    }
+    if !value.contains('"') {
+        return format!("\"{}\"", value);
     }
 
+    let parts: Vec<String> = value.split('\'').map(|part| format!("'{}'", part)).collect();
+    format!("concat({})", parts.join(", \"'\", "))
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::element::{Element, ElementType};
+    use crate::element::{xpath_literal, Element, ElementType};
     use serde_yaml::{Mapping, Value};
 
     #[test]
@@ -214,8 +298,61 @@ mod tests {
         let expected = Ok(Element {
             element_type: ElementType::XPATH,
             value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
+            frame: None,
+        });
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_element_in_frame() {
+        let yaml = "
+        element:
+            id: 'card-number'
+            frame:
+                id: 'payment'
+                frame:
+                    xPath: '//iframe'
+        ";
+        let element: Mapping = serde_yaml::from_str(yaml).unwrap();
+
+        let result = Element::new(&element);
+        let expected = Ok(Element {
+            element_type: ElementType::ID,
+            value: "card-number".to_owned(),
+            frame: Some(Box::new(Element {
+                element_type: ElementType::ID,
+                value: "payment".to_owned(),
+                frame: Some(Box::new(Element {
+                    element_type: ElementType::XPATH,
+                    value: "//iframe".to_owned(),
+                    frame: None,
+                })),
+            })),
         });
 
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn test_locator_in_frame() {
+        let yaml = "
+            id: 'x'
+            frame:
+                id: 'payment'
+        ";
+        let locator: Mapping = serde_yaml::from_str(yaml).unwrap();
+
+        let expected = Err("Element: frame is only supported on the task element".to_string());
+        assert_eq!(expected, Element::from_locator(&locator));
+        assert!(Element::with_frame(&locator).unwrap().frame.is_some());
+    }
+
+    #[test]
+    fn test_xpath_literal() {
+        assert_eq!("'editor'", xpath_literal("editor"));
+        assert_eq!("\"it's\"", xpath_literal("it's"));
+        assert_eq!("'say \"hi\"'", xpath_literal("say \"hi\""));
+        assert_eq!("concat('it', \"'\", 's \"hi\"')", xpath_literal("it's \"hi\""));
+    }
 }
@@ -1,15 +1,26 @@
+use chrono::Local;
 use serde::{Serialize, Deserialize};
+use serde_json::Value;
 use thirtyfour::{Capabilities, DesiredCapabilities, ChromeCapabilities, WebDriver};
 
-use crate::tasks::{to_task, Tasks, TaskOk, TaskResult, TaskErr};
+use crate::element::Element;
+use crate::tasks::{to_task, Tasks, TaskOk, TaskResult, TaskErr, TaskTypes};
+use crate::variables::{resolve_variables, resolve_variables_strict, Variable};
 use std::{path::PathBuf, str::FromStr, fs, collections::HashMap};
 
+const RUN_ID: &str = "run_id";
+const TASK_INDEX: &str = "task_index";
+const TASK_NAME: &str = "task_name";
+/// Set by the runner, tasks can't overwrite them.
+pub const BUILT_IN_VARIABLES: [&str; 3] = [RUN_ID, TASK_INDEX, TASK_NAME];
+
 pub type ExecuteResult = std::result::Result<(WebDriverSession, TaskOk), (WebDriverSession, TaskErr)>;
 
 pub struct Executor {
     pub results: Vec<TaskOk>,
     pub tasks: Tasks,
-    pub config_path: Option<PathBuf>
+    pub config_path: Option<PathBuf>,
+    pub update_baselines: bool,
 }
 
 impl Executor {
@@ -19,24 +30,45 @@ impl Executor {
         Ok(Executor {
             results: vec![],
             tasks: tasks_to_execute,
-            config_path
+            config_path,
+            update_baselines: false,
         })
     }
 
     pub async fn execute(&mut self) -> Result<&Vec<TaskOk>, String> {
         let mut web_driver: WebDriverSession = WebDriverSession::new(&self.config_path).await?;
+        web_driver.update_baselines = self.update_baselines;
+        let run_id = Local::now().format("%Y%m%d-%H%M%S").to_string();
+        web_driver.set_built_in(RUN_ID, Variable::from(run_id));
   
-        for task in self.tasks.iter() {
+        for (index, task) in self.tasks.iter().enumerate() {
+            web_driver.set_built_in(TASK_INDEX, Variable::from((index + 1) as f64));
+            web_driver.set_built_in(TASK_NAME, Variable::from(task.name()));
+            let frames = match task.element() {
+                Some(element) => match Element::enter_frames(element, &web_driver).await {
+                    Ok(frames) => frames,
+                    Err(e) => {
+                        web_driver.driver.quit().await.unwrap();
+                        return Err(e);
+                    }
+                },
+                None => 0,
+            };
+
             let execute = task.execute(web_driver).await;
             match execute {
                 Ok((driver, task_ok)) => {
                     web_driver = driver;
+                    if let Err(e) = Element::leave_frames(frames, &web_driver).await {
+                        web_driver.driver.quit().await.unwrap();
+                        return Err(e);
+                    }
                     self.results.push(task_ok)
                 }
                 Err((web_driver, e)) => {
+                    let e = unexpected_alert(&web_driver, e).await;
                     web_driver.driver.quit().await.unwrap();
-                    println!("{e}");
-                    break;
+                    return Err(e.to_string());
                 },
             }
         }
@@ -44,11 +76,36 @@ impl Executor {
     }
 }
 
+/// With `unexpected_alert: fail` a dialog stays open and the next task fails
+/// on it, the error then names the dialog instead of the WebDriver failure.
+async fn unexpected_alert(web_driver: &WebDriverSession, e: TaskErr) -> TaskErr {
+    if e.get_task_type() == Some(TaskTypes::ALERT) {
+        return e;
+    }
+
+    alert_error(e, web_driver.driver.get_alert_text().await.ok())
+}
+
+/// `alert` is the text of the dialog still open after the failure.
+fn alert_error(e: TaskErr, alert: Option<String>) -> TaskErr {
+    match (e.get_task_type(), alert) {
+        (Some(TaskTypes::ALERT), _) | (_, None) => e,
+        (task_type, Some(text)) => TaskErr::new(
+            format!("Unexpected alert [{}], handle it with an alert task", text),
+            task_type,
+            None,
+        ),
+    }
+}
 
 #[derive(Clone)]
 pub struct WebDriverSession {
     pub driver: WebDriver,
-    pub variables: HashMap<String, String>,
+    pub variables: HashMap<String, Variable>,
+    pub strict_variables: bool,
+    pub on_fail: OnFail,
+    pub update_baselines: bool,
+    pub server_url: String,
 }
 
 impl WebDriverSession {
@@ -62,11 +119,37 @@ impl WebDriverSession {
         };
 
 
-        Ok(WebDriverSession { driver, variables: HashMap::new() })
+        Ok(WebDriverSession {
+            driver,
+            variables: HashMap::new(),
+            strict_variables: config.strict_variables,
+            on_fail: config.on_fail,
+            update_baselines: false,
+            server_url: config.server_url,
+        })
+    }
+
+    /// Sets a variable for the following tasks, built-in variables are
+    /// refused.
+    pub fn add_variable(&mut self, key: &str, value: Variable) -> Result<(), String> {
+        if BUILT_IN_VARIABLES.contains(&key) {
+            return Err(format!("{}: is a built-in variable", key));
+        }
+        self.variables.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn set_built_in(&mut self, key: &str, value: Variable) {
+        self.variables.insert(key.to_string(), value);
     }
 
-    pub fn add_variable(&mut self, key: &String, value: &String) {
-        self.variables.insert(key.to_string(), value.to_string());
+    /// Resolves the variables in `text`, failing on unknown variables when
+    /// `strict_variables` is enabled in the config.
+    pub fn resolve(&self, text: &str) -> Result<String, String> {
+        if self.strict_variables {
+            return resolve_variables_strict(text, &self.variables);
+        }
+        Ok(resolve_variables(text, &self.variables))
     }
 }
 
@@ -88,23 +171,88 @@ impl FromStr for Browser {
         }
     }
 }
+/// What a validation does when one of its checks fails.
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq)]
+pub enum OnFail {
+    STOP,
+    #[default]
+    CONTINUE,
+}
+
+impl FromStr for OnFail {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<OnFail, String> {
+        match input {
+            "stop" => Ok(OnFail::STOP),
+            "continue" => Ok(OnFail::CONTINUE),
+            _ => Err(format!("on_fail: [{}] expected stop or continue", input)),
+        }
+    }
+}
+
+/// What happens to a dialog no `alert` task handled, without it the
+/// WebDriver default applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnexpectedAlert {
+    ACCEPT,
+    DISMISS,
+    FAIL,
+}
+
+impl FromStr for UnexpectedAlert {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<UnexpectedAlert, String> {
+        match input {
+            "accept" => Ok(UnexpectedAlert::ACCEPT),
+            "dismiss" => Ok(UnexpectedAlert::DISMISS),
+            "fail" => Ok(UnexpectedAlert::FAIL),
+            _ => Err(format!("unexpected_alert: [{}] expected accept, dismiss or fail", input)),
+        }
+    }
+}
+
+impl UnexpectedAlert {
+    /// The `unhandledPromptBehavior` capability, `ignore` keeps the dialog
+    /// open so the failing task can report it.
+    fn prompt_behavior(&self) -> &str {
+        match self {
+            UnexpectedAlert::ACCEPT => "accept",
+            UnexpectedAlert::DISMISS => "dismiss",
+            UnexpectedAlert::FAIL => "ignore",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct DriverConfig {
     browser: String,
-    server_url: String
+    server_url: String,
+    #[serde(default)]
+    strict_variables: bool,
+    #[serde(default)]
+    on_fail: Option<String>,
+    #[serde(default)]
+    unexpected_alert: Option<String>,
 }
 impl DriverConfig {
     fn default() -> DriverConfig {
         DriverConfig {
             browser: String::from("firefox"),
-            server_url: String::from("http://localhost:4444")
+            server_url: String::from("http://localhost:4444"),
+            strict_variables: false,
+            on_fail: None,
+            unexpected_alert: None,
         }
     }
 }
 
 struct WebDriverConfig {
     capabilities: Capabilities,
-    server_url: String
+    server_url: String,
+    strict_variables: bool,
+    on_fail: OnFail,
 }
 
 impl WebDriverConfig {
@@ -113,15 +261,31 @@ impl WebDriverConfig {
         let config: DriverConfig = Self::get_config(path)?;
         let browser = Browser::from_str(&config.browser)?;
         let server_url = config.server_url;
+        let on_fail = match &config.on_fail {
+            Some(on_fail) => OnFail::from_str(on_fail)?,
+            None => OnFail::default(),
+        };
+        let unexpected_alert = match &config.unexpected_alert {
+            Some(unexpected_alert) => Some(UnexpectedAlert::from_str(unexpected_alert)?),
+            None => None,
+        };
 
-        let capabilities = match browser {
+        let mut capabilities = match browser {
             Browser::CHROME => Capabilities::from(Self::get_google_capabilities()),
             Browser::FIREFOX => Capabilities::from(DesiredCapabilities::firefox()),
         };
+        if let Some(unexpected_alert) = unexpected_alert {
+            capabilities.insert(
+                "unhandledPromptBehavior".to_string(),
+                Value::from(unexpected_alert.prompt_behavior()),
+            );
+        }
     
         Ok(WebDriverConfig {
             capabilities,
-            server_url
+            server_url,
+            strict_variables: config.strict_variables,
+            on_fail,
         })
     }
 
@@ -156,3 +320,40 @@ impl WebDriverConfig {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_error() {
+        let e = TaskErr::new("no such element".to_string(), Some(TaskTypes::CLICK), None);
+        let result = alert_error(e, Some("Leave the page?".to_string()));
+        assert_eq!(
+            "Unexpected alert [Leave the page?], handle it with an alert task",
+            result.get_message()
+        );
+        assert_eq!(Some(TaskTypes::CLICK), result.get_task_type());
+
+        let e = TaskErr::new("no such element".to_string(), Some(TaskTypes::CLICK), None);
+        assert_eq!("no such element", alert_error(e, None).get_message());
+
+        let e = TaskErr::new("no such alert".to_string(), Some(TaskTypes::ALERT), None);
+        let result = alert_error(e, Some("Leave the page?".to_string()));
+        assert_eq!("no such alert", result.get_message());
+    }
+
+    #[test]
+    fn test_unexpected_alert_capability() {
+        let config = WebDriverConfig::new(&None).unwrap();
+        assert_eq!(None, config.capabilities.get("unhandledPromptBehavior"));
+
+        let path = std::env::temp_dir().join(format!("ls-config-{}.yml", std::process::id()));
+        fs::write(&path, "browser: firefox\nserver_url: http://localhost:4444\nunexpected_alert: fail\n").unwrap();
+        let config = WebDriverConfig::new(&Some(path.clone()));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            Some(&Value::from("ignore")),
+            config.unwrap().capabilities.get("unhandledPromptBehavior")
+        );
+    }
+}
@@ -0,0 +1,473 @@
+use std::{collections::HashMap, fmt};
+
+use crate::variables::{resolve_variable, Variable};
+
+/// Result of evaluating an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionValue {
+    Number(f64),
+    String(String),
+    Bool(bool),
+}
+
+impl ExpressionValue {
+    /// Strings holding a number are treated as numbers so `{price} * 2`
+    /// works on values scraped from the page.
+    fn from_text(text: String) -> ExpressionValue {
+        match text.trim().parse::<f64>() {
+            Ok(number) => ExpressionValue::Number(number),
+            Err(_) => ExpressionValue::String(text),
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            ExpressionValue::Number(n) => *n != 0.0,
+            ExpressionValue::String(s) => !s.is_empty(),
+            ExpressionValue::Bool(b) => *b,
+        }
+    }
+
+    fn as_number(&self, operator: &str) -> Result<f64, String> {
+        match self {
+            ExpressionValue::Number(n) => Ok(*n),
+            _ => Err(format!("[{}] is not a number for operator {}", self, operator)),
+        }
+    }
+}
+
+impl From<Variable> for ExpressionValue {
+    fn from(variable: Variable) -> Self {
+        match variable {
+            Variable::Bool(b) => ExpressionValue::Bool(b),
+            Variable::Number(_) => ExpressionValue::Number(variable.as_f64().unwrap_or(f64::NAN)),
+            Variable::String(s) => ExpressionValue::from_text(s),
+            _ => ExpressionValue::String(variable.to_string()),
+        }
+    }
+}
+
+impl From<ExpressionValue> for Variable {
+    fn from(value: ExpressionValue) -> Self {
+        match value {
+            ExpressionValue::Number(n) => Variable::from(n),
+            ExpressionValue::String(s) => Variable::from(s),
+            ExpressionValue::Bool(b) => Variable::from(b),
+        }
+    }
+}
+
+impl fmt::Display for ExpressionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpressionValue::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => {
+                write!(f, "{}", *n as i64)
+            }
+            ExpressionValue::Number(n) => write!(f, "{}", n),
+            ExpressionValue::String(s) => write!(f, "{}", s),
+            ExpressionValue::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Variable(String),
+    Operator(&'static str),
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Value(ExpressionValue),
+    Variable(String),
+    Unary(&'static str, Box<Node>),
+    Binary(&'static str, Box<Node>, Box<Node>),
+}
+
+/// A parsed expression, e.g. `{price} * {qty} + 1` or `{total} >= 10 and {ok}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    root: Node,
+}
+
+// Parsed numbers are never NaN.
+impl Eq for Expression {}
+
+// Longest operators first so `<=` is not read as `<`.
+const OPERATORS: [&str; 15] = [
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "+", "-", "*", "/", "%", "!", "=",
+];
+
+// Binding power of each binary operator, lowest first.
+const PRECEDENCE: [&[&str]; 6] = [
+    &["||"],
+    &["&&"],
+    &["==", "!="],
+    &["<", "<=", ">", ">="],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+// `not` sits below the comparisons, `not {a} == {b}` negates the comparison.
+// `!` binds to the value next to it like `-`.
+const NOT_LEVEL: usize = 2;
+
+impl Expression {
+    pub fn parse(text: &str) -> Result<Expression, String> {
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() {
+            return Err("Expression is empty".to_string());
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let root = parser.binary(0)?;
+
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(format!("Unexpected {:?} in expression", token));
+        }
+
+        Ok(Expression { root })
+    }
+
+    pub fn evaluate(&self, vars: &HashMap<String, Variable>) -> Result<ExpressionValue, String> {
+        evaluate_node(&self.root, vars)
+    }
+}
+
+/// Parses and evaluates `text` in one go.
+pub fn evaluate(text: &str, vars: &HashMap<String, Variable>) -> Result<ExpressionValue, String> {
+    Expression::parse(text)?.evaluate(vars)
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+
+        if c.is_whitespace() {
+            pos += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || (c == '.' && chars.get(pos + 1).is_some_and(char::is_ascii_digit)) {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            let number: String = chars[start..pos].iter().collect();
+            match number.parse::<f64>() {
+                Ok(n) => tokens.push(Token::Number(n)),
+                Err(_) => return Err(format!("[{}] is not a number", number)),
+            }
+            continue;
+        }
+
+        if c == '\'' || c == '"' {
+            let mut string = String::new();
+            pos += 1;
+            loop {
+                match chars.get(pos) {
+                    None => return Err("Unterminated string in expression".to_string()),
+                    Some('\\') if pos + 1 < chars.len() => {
+                        string.push(chars[pos + 1]);
+                        pos += 2;
+                    }
+                    Some(q) if *q == c => {
+                        pos += 1;
+                        break;
+                    }
+                    Some(other) => {
+                        string.push(*other);
+                        pos += 1;
+                    }
+                }
+            }
+            tokens.push(Token::String(string));
+            continue;
+        }
+
+        if c == '{' {
+            let start = pos;
+            let mut depth = 0;
+            while pos < chars.len() {
+                match chars[pos] {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+                pos += 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            if depth != 0 {
+                return Err("Unterminated variable in expression".to_string());
+            }
+            tokens.push(Token::Variable(chars[start..pos].iter().collect()));
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() {
+            let start = pos;
+            while pos < chars.len() && chars[pos].is_ascii_alphabetic() {
+                pos += 1;
+            }
+            let word: String = chars[start..pos].iter().collect();
+            let token = match word.as_str() {
+                "true" => Token::Bool(true),
+                "false" => Token::Bool(false),
+                "and" => Token::Operator("&&"),
+                "or" => Token::Operator("||"),
+                "not" => Token::Operator("not"),
+                _ => return Err(format!("Unknown word [{}] in expression, use {{{}}} for variables", word, word)),
+            };
+            tokens.push(token);
+            continue;
+        }
+
+        match c {
+            '(' => {
+                tokens.push(Token::Open);
+                pos += 1;
+                continue;
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                pos += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let rest: String = chars[pos..chars.len().min(pos + 2)].iter().collect();
+        match OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+            Some(&"=") => return Err("Use == to compare values".to_string()),
+            Some(op) => {
+                tokens.push(Token::Operator(op));
+                pos += op.len();
+            }
+            None => return Err(format!("Unexpected [{}] in expression", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn binary(&mut self, level: usize) -> Result<Node, String> {
+        if level == PRECEDENCE.len() {
+            return self.unary();
+        }
+
+        if level == NOT_LEVEL && self.tokens.get(self.pos) == Some(&Token::Operator("not")) {
+            self.pos += 1;
+            return Ok(Node::Unary("!", Box::new(self.binary(level)?)));
+        }
+
+        let mut left = self.binary(level + 1)?;
+        while let Some(Token::Operator(op)) = self.tokens.get(self.pos) {
+            if !PRECEDENCE[level].contains(op) {
+                break;
+            }
+            let op = *op;
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        if let Some(Token::Operator(op)) = self.tokens.get(self.pos) {
+            if *op == "-" || *op == "!" {
+                let op = *op;
+                self.pos += 1;
+                return Ok(Node::Unary(op, Box::new(self.unary()?)));
+            }
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Node, String> {
+        let token = match self.tokens.get(self.pos) {
+            Some(token) => token.clone(),
+            None => return Err("Unexpected end of expression".to_string()),
+        };
+        self.pos += 1;
+
+        match token {
+            Token::Number(n) => Ok(Node::Value(ExpressionValue::Number(n))),
+            Token::String(s) => Ok(Node::Value(ExpressionValue::String(s))),
+            Token::Bool(b) => Ok(Node::Value(ExpressionValue::Bool(b))),
+            Token::Variable(v) => Ok(Node::Variable(v)),
+            Token::Open => {
+                let node = self.binary(0)?;
+                match self.tokens.get(self.pos) {
+                    Some(Token::Close) => {
+                        self.pos += 1;
+                        Ok(node)
+                    }
+                    _ => Err("Missing ) in expression".to_string()),
+                }
+            }
+            token => Err(format!("Unexpected {:?} in expression", token)),
+        }
+    }
+}
+
+fn evaluate_node(node: &Node, vars: &HashMap<String, Variable>) -> Result<ExpressionValue, String> {
+    match node {
+        Node::Value(value) => Ok(value.clone()),
+        Node::Variable(raw) => Ok(ExpressionValue::from(resolve_variable(raw, vars)?)),
+        Node::Unary("-", value) => Ok(ExpressionValue::Number(-evaluate_node(value, vars)?.as_number("-")?)),
+        Node::Unary(_, value) => Ok(ExpressionValue::Bool(!evaluate_node(value, vars)?.is_truthy())),
+        Node::Binary("&&", left, right) => {
+            if !evaluate_node(left, vars)?.is_truthy() {
+                return Ok(ExpressionValue::Bool(false));
+            }
+            Ok(ExpressionValue::Bool(evaluate_node(right, vars)?.is_truthy()))
+        }
+        Node::Binary("||", left, right) => {
+            if evaluate_node(left, vars)?.is_truthy() {
+                return Ok(ExpressionValue::Bool(true));
+            }
+            Ok(ExpressionValue::Bool(evaluate_node(right, vars)?.is_truthy()))
+        }
+        Node::Binary(op, left, right) => {
+            let left = evaluate_node(left, vars)?;
+            let right = evaluate_node(right, vars)?;
+            binary(op, left, right)
+        }
+    }
+}
+
+fn binary(op: &str, left: ExpressionValue, right: ExpressionValue) -> Result<ExpressionValue, String> {
+    use ExpressionValue::{Bool, Number};
+
+    match op {
+        "+" => match (&left, &right) {
+            (Number(l), Number(r)) => Ok(Number(l + r)),
+            _ => Ok(ExpressionValue::String(format!("{}{}", left, right))),
+        },
+        "-" => Ok(Number(left.as_number(op)? - right.as_number(op)?)),
+        "*" => Ok(Number(left.as_number(op)? * right.as_number(op)?)),
+        "/" | "%" => {
+            let divisor = right.as_number(op)?;
+            if divisor == 0.0 {
+                return Err("Division by zero".to_string());
+            }
+            let dividend = left.as_number(op)?;
+            match op {
+                "/" => Ok(Number(dividend / divisor)),
+                _ => Ok(Number(dividend % divisor)),
+            }
+        }
+        "==" => Ok(Bool(equals(&left, &right))),
+        "!=" => Ok(Bool(!equals(&left, &right))),
+        _ => {
+            let ordering = match (&left, &right) {
+                (Number(l), Number(r)) => l.partial_cmp(r),
+                _ => Some(left.to_string().cmp(&right.to_string())),
+            };
+            let ordering = match ordering {
+                Some(ordering) => ordering,
+                None => return Err(format!("Unable to compare [{}] and [{}]", left, right)),
+            };
+            Ok(Bool(match op {
+                "<" => ordering.is_lt(),
+                "<=" => ordering.is_le(),
+                ">" => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+    }
+}
+
+fn equals(left: &ExpressionValue, right: &ExpressionValue) -> bool {
+    match (left, right) {
+        (ExpressionValue::Number(l), ExpressionValue::Number(r)) => l == r,
+        _ => left.to_string() == right.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<String, Variable> {
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("price".to_string(), Variable::from("2.5"));
+        vars.insert("qty".to_string(), Variable::from(4.0));
+        vars.insert("name".to_string(), Variable::from("Foo"));
+        vars.insert("active".to_string(), Variable::from(true));
+        vars
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let result = evaluate("{price} * {qty} + 1", &vars()).unwrap();
+        assert_eq!("11", result.to_string());
+
+        let result = evaluate("({price} + 0.5) * -{qty} / 3", &vars()).unwrap();
+        assert_eq!("-4", result.to_string());
+
+        let result = evaluate("7 % 4", &vars()).unwrap();
+        assert_eq!(ExpressionValue::Number(3.0), result);
+    }
+
+    #[test]
+    fn test_concat() {
+        let result = evaluate("'Hi, ' + {name} + ' x' + {qty}", &vars()).unwrap();
+        assert_eq!(ExpressionValue::String("Hi, Foo x4".to_string()), result);
+    }
+
+    fn condition(text: &str) -> Result<bool, String> {
+        evaluate(text, &vars()).map(|value| value.is_truthy())
+    }
+
+    #[test]
+    fn test_comparison_and_logic() {
+        assert_eq!(Ok(true), condition("{qty} >= 4 && {name} == 'Foo'"));
+        assert_eq!(Ok(false), condition("{qty} < 4 or not ({name} == 'Foo')"));
+        assert_eq!(Ok(true), condition("{missing ?? 0} == 0"));
+        assert_eq!(Ok(true), condition("{active} && {qty} == 4"));
+    }
+
+    #[test]
+    fn test_not_precedence() {
+        assert_eq!(Ok(false), condition("not {qty} == 4"));
+        assert_eq!(Ok(true), condition("{active} and not {name} == 'Bar'"));
+        assert_eq!(Ok(true), condition("!{active} == false"));
+        assert!(Expression::parse("{qty} == not 4").is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Err("Division by zero".to_string()), evaluate("1 / 0", &vars()));
+        assert_eq!(
+            Err("[Foo] is not a number for operator *".to_string()),
+            evaluate("{name} * 2", &vars())
+        );
+        assert_eq!(
+            Err("Undefined variable: missing".to_string()),
+            evaluate("{missing} + 1", &vars())
+        );
+        assert_eq!(Err("Missing ) in expression".to_string()), Expression::parse("(1 + 2"));
+        assert_eq!(Err("Use == to compare values".to_string()), Expression::parse("1 = 2"));
+        assert!(Expression::parse("price * 2").is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use thirtyfour::{action_chain::ActionChain, Key};
+
+const KEY_PREFIX: &str = "{key:";
+
+/// A part of a `send_key` input, plain text or a named key like `{ENTER}`
+/// or `{CTRL+A}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyInput {
+    Text(String),
+    /// Modifiers held while the last key is pressed.
+    Chord(Vec<char>, char),
+}
+
+/// Splits `input` on named keys. A bare `{END}` is the variable when
+/// `is_variable` knows it, `{key:END}` is always the key. Everything else,
+/// escaped `{{` included, stays in the text.
+pub fn parse_keys(input: &str, is_variable: impl Fn(&str) -> bool) -> Result<Vec<KeyInput>, String> {
+    let mut keys: Vec<KeyInput> = Vec::new();
+    let mut text = String::new();
+    let mut rest = input;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") {
+            text.push_str("{{");
+            rest = &rest[2..];
+            continue;
+        }
+
+        let (chord, end) = if let Some(name) = rest.strip_prefix(KEY_PREFIX) {
+            let end = match name.find('}') {
+                Some(end) => end,
+                None => return Err(format!("Missing }} after {}{}", KEY_PREFIX, name)),
+            };
+            match to_chord(&name[..end], false) {
+                Some(chord) => (chord, KEY_PREFIX.len() + end + 1),
+                None => return Err(format!("Unknown key [{}]", &name[..end])),
+            }
+        } else {
+            let bare = match (c, rest.find('}')) {
+                ('{', Some(end)) if !is_variable(rest[1..end].trim()) => {
+                    to_chord(&rest[1..end], true).map(|chord| (chord, end + 1))
+                }
+                _ => None,
+            };
+            match bare {
+                Some(bare) => bare,
+                None => {
+                    text.push(c);
+                    rest = &rest[c.len_utf8()..];
+                    continue;
+                }
+            }
+        };
+
+        if !text.is_empty() {
+            keys.push(KeyInput::Text(std::mem::take(&mut text)));
+        }
+        keys.push(chord);
+        rest = &rest[end..];
+    }
+
+    if !text.is_empty() {
+        keys.push(KeyInput::Text(text));
+    }
+    Ok(keys)
+}
+
+/// Text for `WebElement::send_keys`, modifiers stay down until the NULL key.
+pub fn to_text(keys: &[KeyInput]) -> String {
+    let mut text = String::new();
+    for key in keys {
+        match key {
+            KeyInput::Text(value) => text.push_str(value),
+            KeyInput::Chord(modifiers, key) if modifiers.is_empty() => text.push(*key),
+            KeyInput::Chord(modifiers, key) => {
+                text.extend(modifiers);
+                text.push(*key);
+                text.push(Key::Null.into());
+            }
+        }
+    }
+    text
+}
+
+/// Adds the keys to an action chain, for typing without an element.
+pub fn to_actions(mut actions: ActionChain, keys: &[KeyInput]) -> ActionChain {
+    for key in keys {
+        match key {
+            KeyInput::Text(value) => actions = actions.send_keys(value),
+            KeyInput::Chord(modifiers, key) => {
+                for modifier in modifiers {
+                    actions = actions.key_down(*modifier);
+                }
+                actions = actions.key_down(*key).key_up(*key);
+                for modifier in modifiers.iter().rev() {
+                    actions = actions.key_up(*modifier);
+                }
+            }
+        }
+    }
+    actions
+}
+
+/// `ENTER`, `SHIFT+TAB`, `CTRL+A`. A `bare` single character is only a key
+/// after a modifier, so `{a}` stays a variable.
+fn to_chord(token: &str, bare: bool) -> Option<KeyInput> {
+    let parts: Vec<&str> = token.split('+').map(str::trim).collect();
+    let (last, modifiers) = parts.split_last()?;
+
+    let modifiers: Option<Vec<char>> = modifiers.iter().map(|m| to_modifier(m)).collect();
+    let modifiers = modifiers?;
+
+    let key = match (named_key(last), last.chars().count()) {
+        (Some(key), _) => key,
+        (None, 1) if !bare || !modifiers.is_empty() => last.chars().next()?.to_ascii_lowercase(),
+        _ => return None,
+    };
+
+    Some(KeyInput::Chord(modifiers, key))
+}
+
+fn to_modifier(name: &str) -> Option<char> {
+    let key = match name {
+        "CTRL" | "CONTROL" => Key::Control,
+        "SHIFT" => Key::Shift,
+        "ALT" => Key::Alt,
+        "META" | "CMD" | "COMMAND" => Key::Meta,
+        _ => return None,
+    };
+    Some(key.into())
+}
+
+fn named_key(name: &str) -> Option<char> {
+    if let Some(modifier) = to_modifier(name) {
+        return Some(modifier);
+    }
+
+    let key = match name {
+        "ENTER" => Key::Enter,
+        "RETURN" => Key::Return,
+        "TAB" => Key::Tab,
+        "ESC" | "ESCAPE" => Key::Escape,
+        "SPACE" => Key::Space,
+        "BACKSPACE" => Key::Backspace,
+        "DELETE" => Key::Delete,
+        "INSERT" => Key::Insert,
+        "HOME" => Key::Home,
+        "END" => Key::End,
+        "PAGE_UP" => Key::PageUp,
+        "PAGE_DOWN" => Key::PageDown,
+        "UP" => Key::Up,
+        "DOWN" => Key::Down,
+        "LEFT" => Key::Left,
+        "RIGHT" => Key::Right,
+        "F1" => Key::F1,
+        "F2" => Key::F2,
+        "F3" => Key::F3,
+        "F4" => Key::F4,
+        "F5" => Key::F5,
+        "F6" => Key::F6,
+        "F7" => Key::F7,
+        "F8" => Key::F8,
+        "F9" => Key::F9,
+        "F10" => Key::F10,
+        "F11" => Key::F11,
+        "F12" => Key::F12,
+        _ => return None,
+    };
+    Some(key.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: Key) -> char {
+        key.into()
+    }
+
+    fn parse(input: &str) -> Result<Vec<KeyInput>, String> {
+        parse_keys(input, |_| false)
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        let expected = Ok(vec![
+            KeyInput::Text("Rust".to_string()),
+            KeyInput::Chord(Vec::new(), key(Key::Enter)),
+        ]);
+        assert_eq!(expected, parse("Rust{ENTER}"));
+        assert_eq!(expected, parse("Rust{key:ENTER}"));
+
+        let expected = Ok(vec![
+            KeyInput::Chord(vec![key(Key::Control)], 'a'),
+            KeyInput::Chord(vec![key(Key::Shift)], key(Key::Tab)),
+        ]);
+        assert_eq!(expected, parse("{CTRL+A}{SHIFT + TAB}"));
+    }
+
+    #[test]
+    fn test_parse_keys_variables() {
+        let expected = Ok(vec![KeyInput::Text("{{ENTER} {a} {name} {user.email}".to_string())]);
+        assert_eq!(expected, parse("{{ENTER} {a} {name} {user.email}"));
+
+        let is_variable = |name: &str| name == "END";
+        let expected = Ok(vec![
+            KeyInput::Text("{END}".to_string()),
+            KeyInput::Chord(Vec::new(), key(Key::End)),
+            KeyInput::Chord(Vec::new(), key(Key::Home)),
+        ]);
+        assert_eq!(expected, parse_keys("{END}{key:END}{HOME}", is_variable));
+    }
+
+    #[test]
+    fn test_parse_keys_errors() {
+        assert_eq!(Err("Unknown key [ENTRE]".to_string()), parse("{key:ENTRE}"));
+        assert_eq!(Err("Missing } after {key:ENTER".to_string()), parse("{key:ENTER"));
+        assert_eq!(Ok(vec![KeyInput::Text("{ENTRE}".to_string())]), parse("{ENTRE}"));
+    }
+
+    #[test]
+    fn test_to_text() {
+        let keys = parse("a{CTRL+C}{ENTER}").unwrap();
+        let expected = format!(
+            "a{}c{}{}",
+            key(Key::Control),
+            key(Key::Null),
+            key(Key::Enter)
+        );
+        assert_eq!(expected, to_text(&keys));
+    }
+}
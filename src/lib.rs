@@ -1,4 +1,7 @@
 pub mod tasks;
 pub mod executor;
 pub mod element;
-pub mod variables;
\ No newline at end of file
+pub mod variables;
+pub mod expression;
+pub mod matcher;
+pub mod keys;
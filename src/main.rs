@@ -17,6 +17,10 @@ struct Args {
     /// Path to config file
     #[arg(short, long)]
     config_path: Option<PathBuf>,
+
+    /// Replace screenshot baselines with the new screenshots
+    #[arg(long)]
+    update_baselines: bool,
 }
 #[tokio::main]
 async fn main() {
@@ -30,10 +34,15 @@ async fn main() {
             process::exit(1);
         }
     };
+    executor.update_baselines = args.update_baselines;
 
 
     match executor.execute().await {
         Ok(x) => println!("{:#?}", x),
-        Err(x) => println!("{}", x)
+        Err(x) => {
+            println!("{:#?}", executor.results);
+            println!("{}", x);
+            process::exit(1);
+        }
     };
 }
@@ -0,0 +1,322 @@
+use regex::Regex;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::variables::{has_variables, resolve_variables, Filter};
+
+/// How an expected value is compared to the actual one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatcherType {
+    Equals,
+    NotEquals,
+    Contains,
+    StartsWith,
+    Matches,
+    CaseInsensitive,
+    GreaterThan,
+    LessThan,
+    Between,
+}
+
+impl FromStr for MatcherType {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<MatcherType, Self::Err> {
+        match input {
+            "equals" => Ok(MatcherType::Equals),
+            "not_equals" => Ok(MatcherType::NotEquals),
+            "contains" => Ok(MatcherType::Contains),
+            "starts_with" => Ok(MatcherType::StartsWith),
+            "matches" => Ok(MatcherType::Matches),
+            "case_insensitive" => Ok(MatcherType::CaseInsensitive),
+            "gt" => Ok(MatcherType::GreaterThan),
+            "lt" => Ok(MatcherType::LessThan),
+            "between" => Ok(MatcherType::Between),
+            _ => Err(format!("Unknown matcher: {}", input)),
+        }
+    }
+}
+
+impl MatcherType {
+    fn verb(&self) -> &str {
+        match self {
+            MatcherType::Equals => "is",
+            MatcherType::NotEquals => "is not",
+            MatcherType::Contains => "contains",
+            MatcherType::StartsWith => "starts with",
+            MatcherType::Matches => "matches",
+            MatcherType::CaseInsensitive => "is (case insensitive)",
+            MatcherType::GreaterThan => "is greater than",
+            MatcherType::LessThan => "is less than",
+            MatcherType::Between => "is between",
+        }
+    }
+}
+
+/// An expectation written either as a plain string (`text: 'Rust'`) or as a
+/// single key map (`text: { contains: 'Rust' }`).
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    pub matcher_type: MatcherType,
+    pub expected: String,
+    /// `matches` pattern, compiled up front unless it uses variables.
+    regex: Option<Regex>,
+    /// `between` bounds.
+    range: Option<(f64, f64)>,
+}
+
+impl PartialEq for Matcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.matcher_type == other.matcher_type
+            && self.expected == other.expected
+            && self.range == other.range
+    }
+}
+
+// Bounds are read from yaml numbers, never NaN.
+impl Eq for Matcher {}
+
+impl Matcher {
+    pub fn new(value: &Value) -> Result<Self, String> {
+        if let Some(expected) = value.as_str() {
+            return Ok(Matcher::equals(expected.to_string()));
+        }
+
+        let mapping = match value.as_mapping() {
+            Some(mapping) => mapping,
+            None => return Err(format!("Value: {:?} is not a string", value)),
+        };
+
+        if mapping.len() != 1 {
+            return Err("Matcher must have exactly one key".to_string());
+        }
+
+        let (key, expected) = mapping.iter().last().unwrap();
+        let matcher_type = match key.as_str() {
+            Some(key) => MatcherType::from_str(key)?,
+            None => return Err(format!("Key: {:?} is not a string", key)),
+        };
+
+        if matcher_type == MatcherType::Between {
+            return Matcher::between(expected);
+        }
+
+        let expected = match as_string(expected) {
+            Some(expected) => expected,
+            None => return Err(format!("Value: {:?} is not a string", expected)),
+        };
+
+        // Patterns with variables can only be compiled once they are resolved.
+        let regex = match matcher_type {
+            MatcherType::Matches if !has_variables(&expected) => {
+                Some(to_regex(&resolve_variables(&expected, &HashMap::new()))?)
+            }
+            _ => None,
+        };
+
+        Ok(Matcher {
+            matcher_type,
+            expected,
+            regex,
+            range: None,
+        })
+    }
+
+    /// Exact match, the same as a plain string.
+    pub fn equals(expected: String) -> Self {
+        Matcher {
+            matcher_type: MatcherType::Equals,
+            expected,
+            regex: None,
+            range: None,
+        }
+    }
+
+    fn between(range: &Value) -> Result<Self, String> {
+        let bounds = match range.as_sequence().map(|range| range.as_slice()) {
+            Some([min, max]) => (min.as_f64(), max.as_f64()),
+            _ => (None, None),
+        };
+
+        match bounds {
+            (Some(min), Some(max)) if min <= max => Ok(Matcher {
+                matcher_type: MatcherType::Between,
+                expected: format!("{} and {}", min, max),
+                regex: None,
+                range: Some((min, max)),
+            }),
+            (Some(_), Some(_)) => Err("between - min is greater than max".to_string()),
+            _ => Err("between - expected a list of two numbers [min, max]".to_string()),
+        }
+    }
+
+    /// Same matcher with a new expected value, used once variables are resolved.
+    pub fn with_expected(&self, expected: String) -> Self {
+        Matcher {
+            matcher_type: self.matcher_type,
+            expected,
+            regex: self.regex.clone(),
+            range: self.range,
+        }
+    }
+
+    pub fn is_match(&self, actual: &str) -> Result<bool, String> {
+        let expected = &self.expected;
+        let result = match self.matcher_type {
+            MatcherType::Equals => actual.eq(expected),
+            MatcherType::NotEquals => actual.ne(expected),
+            MatcherType::Contains => actual.contains(expected.as_str()),
+            MatcherType::StartsWith => actual.starts_with(expected.as_str()),
+            MatcherType::CaseInsensitive => actual.to_lowercase().eq(&expected.to_lowercase()),
+            MatcherType::Matches => match &self.regex {
+                Some(regex) => regex.is_match(actual),
+                None => to_regex(expected)?.is_match(actual),
+            },
+            MatcherType::GreaterThan => to_number(actual)? > to_number(expected)?,
+            MatcherType::LessThan => to_number(actual)? < to_number(expected)?,
+            MatcherType::Between => match self.range {
+                Some((min, max)) => {
+                    let actual = to_number(actual)?;
+                    min <= actual && actual <= max
+                }
+                None => return Err(format!("Invalid range [{}]", expected)),
+            },
+        };
+
+        Ok(result)
+    }
+
+    /// `is Rust`, `contains Rust`, used in pass messages.
+    pub fn pass_message(&self) -> String {
+        format!("{} {}", self.matcher_type.verb(), self.expected)
+    }
+
+    /// `[Rust]`, `contains [Rust]`, used in failure messages.
+    pub fn fail_message(&self) -> String {
+        match self.matcher_type {
+            MatcherType::Equals => format!("[{}]", self.expected),
+            _ => format!("{} [{}]", self.matcher_type.verb(), self.expected),
+        }
+    }
+}
+
+/// Matcher values may be numbers, `gt: 10`.
+fn as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn to_regex(pattern: &str) -> Result<Regex, String> {
+    match Regex::new(pattern) {
+        Ok(regex) => Ok(regex),
+        Err(e) => Err(format!("Invalid regex [{}]: {}", pattern, e)),
+    }
+}
+
+/// Same rules as the `number` filter, so `$ 1,234.50` is `1234.5`.
+fn to_number(text: &str) -> Result<f64, String> {
+    match Filter::Number.apply(text).map(|n| n.parse::<f64>()) {
+        Ok(Ok(number)) => Ok(number),
+        _ => Err(format!("[{}] is not a number", text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variables::resolve_variables_strict;
+
+    fn matcher(yaml: &str) -> Result<Matcher, String> {
+        let value: Value = serde_yaml::from_str(yaml).unwrap();
+        Matcher::new(&value)
+    }
+
+    #[test]
+    fn test_new() {
+        let expected = Ok(Matcher::equals("Rust".to_string()));
+        assert_eq!(expected, matcher("'Rust'"));
+
+        let result = matcher("starts_with: 'Ru'").unwrap();
+        assert_eq!(MatcherType::StartsWith, result.matcher_type);
+        assert_eq!("Ru", result.expected);
+    }
+
+    #[test]
+    fn test_new_errors() {
+        assert_eq!(Err("Value: Number(2) is not a string".to_string()), matcher("2"));
+        assert_eq!(Err("Unknown matcher: ends_with".to_string()), matcher("ends_with: 'a'"));
+        assert_eq!(
+            Err("Matcher must have exactly one key".to_string()),
+            matcher("{ contains: 'a', equals: 'b' }")
+        );
+        assert_eq!(
+            Err("between - expected a list of two numbers [min, max]".to_string()),
+            matcher("between: 3")
+        );
+        assert_eq!(
+            Err("between - expected a list of two numbers [min, max]".to_string()),
+            matcher("between: ['1', 'ten']")
+        );
+        assert_eq!(
+            Err("between - min is greater than max".to_string()),
+            matcher("between: [3, 1]")
+        );
+        assert!(matcher("matches: '('").is_err());
+    }
+
+    #[test]
+    fn test_numbers() {
+        let between = matcher("between: [1, 10.5]").unwrap();
+        assert_eq!(Some((1.0, 10.5)), between.range);
+        assert_eq!("is between 1 and 10.5", between.pass_message());
+
+        assert_eq!(Ok(true), matcher("gt: 1000").unwrap().is_match("$ 1,234.50"));
+        assert_eq!(Ok(false), matcher("lt: 3").unwrap().is_match("3"));
+        assert_eq!(Ok(true), matcher("between: [1, 3]").unwrap().is_match("3 rows"));
+        assert_eq!(Ok(true), matcher("gt: 1").unwrap().is_match("1299.00"));
+        assert!(matcher("gt: 1").unwrap().is_match("Page 2 of 10").is_err());
+        assert_eq!(Ok(true), matcher("equals: 3").unwrap().is_match("3"));
+
+        let expected = Err("[none] is not a number".to_string());
+        assert_eq!(expected, matcher("gt: 2").unwrap().is_match("none"));
+    }
+
+    #[test]
+    fn test_is_match() {
+        assert_eq!(Ok(true), matcher("contains: 'us'").unwrap().is_match("Rust"));
+        assert_eq!(Ok(false), matcher("not_equals: 'Rust'").unwrap().is_match("Rust"));
+        assert_eq!(Ok(true), matcher("case_insensitive: 'rUST'").unwrap().is_match("Rust"));
+        assert_eq!(Ok(true), matcher("matches: '^\\d+ results$'").unwrap().is_match("42 results"));
+        assert_eq!(Ok(true), matcher("matches: '^\\d{{3}$'").unwrap().is_match("123"));
+
+        let pattern = matcher("matches: '^{prefix}-\\d+$'").unwrap();
+        assert_eq!(None, pattern.regex.as_ref().map(|regex| regex.as_str()));
+        let pattern = pattern.with_expected("^ID-\\d+$".to_string());
+        assert_eq!(Ok(true), pattern.is_match("ID-42"));
+        assert!(pattern.with_expected("(".to_string()).is_match("Rust").is_err());
+    }
+
+    #[test]
+    fn test_regex_count_strict() {
+        let pattern = matcher("matches: '^\\d{3}-[A-Z]{2,}$'").unwrap();
+        assert_eq!(Some("^\\d{3}-[A-Z]{2,}$"), pattern.regex.as_ref().map(|regex| regex.as_str()));
+
+        let expected = resolve_variables_strict(&pattern.expected, &HashMap::new()).unwrap();
+        assert_eq!(Ok(true), pattern.with_expected(expected).is_match("123-AB"));
+    }
+
+    #[test]
+    fn test_messages() {
+        let equals = matcher("'Rust'").unwrap();
+        assert_eq!("is Rust", equals.pass_message());
+        assert_eq!("[Rust]", equals.fail_message());
+
+        let contains = matcher("contains: 'Ru'").unwrap();
+        assert_eq!("contains Ru", contains.pass_message());
+        assert_eq!("contains [Ru]", contains.fail_message());
+    }
+}
@@ -1,13 +1,30 @@
+mod alert;
 mod click;
 mod close;
+mod cookies;
+mod execute_script;
 mod link;
+mod mouse;
+mod navigate;
+mod press_keys;
 mod screenshot;
+mod scroll;
+mod select;
 mod send_key;
 mod set_variable;
+mod storage;
+mod switch_frame;
+mod upload;
 mod validate;
+mod validate_table;
 mod wait;
+mod window;
 
-use crate::executor::{ExecuteResult, WebDriverSession};
+use crate::element::Element;
+use crate::executor::{ExecuteResult, OnFail, WebDriverSession};
+use crate::keys::{parse_keys, KeyInput};
+use crate::matcher::Matcher;
+use crate::variables::get_variable;
 use serde::{Deserialize, Serialize};
 use serde_yaml::{Mapping, Value};
 use std::collections::HashMap;
@@ -15,14 +32,27 @@ use std::fmt;
 use std::str::FromStr;
 use std::{fs, path::PathBuf};
 
+use self::alert::Alert;
 use self::click::Click;
 use self::close::Close;
+use self::cookies::Cookies;
+use self::execute_script::ExecuteScript;
 use self::link::Link;
+use self::mouse::Mouse;
+use self::navigate::Navigate;
+use self::press_keys::PressKeys;
 use self::screenshot::Screenshot;
+use self::scroll::Scroll;
+use self::select::Select;
 use self::send_key::SendKey;
 use self::set_variable::SetVars;
+use self::storage::Storage;
+use self::switch_frame::SwitchFrame;
+use self::upload::Upload;
 use self::validate::Validate;
+use self::validate_table::ValidateTable;
 use self::wait::Wait;
+use self::window::Window;
 use async_trait::async_trait;
 use core::fmt::Debug;
 
@@ -37,6 +67,13 @@ pub trait Task {
     fn new(task: &HashMap<String, Value>) -> TaskResult<Self>
     where
         Self: Sized;
+    /// The `name` of the task, `{task_name}` while it runs.
+    fn name(&self) -> &str;
+    /// The element the task works on, the executor enters its `frame` before
+    /// the task runs and leaves it afterwards.
+    fn element(&self) -> Option<&Element> {
+        None
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
@@ -49,12 +86,32 @@ pub struct TaskData {
 pub enum TaskTypes {
     CLICK,
     SENDKEY,
+    PRESSKEYS,
     LINK,
     CLOSE,
     WAIT,
     SCREENSHOT,
+    SELECT,
+    MOUSE,
+    SCROLL,
+    UPLOAD,
+    SWITCHFRAME,
+    SWITCHTOPARENT,
+    DEFAULTCONTENT,
+    WINDOW,
+    ALERT,
+    BACK,
+    FORWARD,
+    REFRESH,
+    COOKIE,
+    SAVECOOKIES,
+    LOADCOOKIES,
+    LOCALSTORAGE,
+    SESSIONSTORAGE,
     VALIDATE,
+    VALIDATETABLE,
     SETVARIABLE,
+    EXECUTESCRIPT,
     #[default]
     NONE,
 }
@@ -66,12 +123,32 @@ impl FromStr for TaskTypes {
         match input {
             "click" => Ok(TaskTypes::CLICK),
             "send_key" => Ok(TaskTypes::SENDKEY),
+            "press_keys" => Ok(TaskTypes::PRESSKEYS),
             "link" => Ok(TaskTypes::LINK),
             "close" => Ok(TaskTypes::CLOSE),
             "wait" => Ok(TaskTypes::WAIT),
             "screenshot" => Ok(TaskTypes::SCREENSHOT),
+            "select" => Ok(TaskTypes::SELECT),
+            "mouse" => Ok(TaskTypes::MOUSE),
+            "scroll" => Ok(TaskTypes::SCROLL),
+            "upload" => Ok(TaskTypes::UPLOAD),
+            "switch_frame" => Ok(TaskTypes::SWITCHFRAME),
+            "switch_to_parent" => Ok(TaskTypes::SWITCHTOPARENT),
+            "default_content" => Ok(TaskTypes::DEFAULTCONTENT),
+            "window" => Ok(TaskTypes::WINDOW),
+            "alert" => Ok(TaskTypes::ALERT),
+            "back" => Ok(TaskTypes::BACK),
+            "forward" => Ok(TaskTypes::FORWARD),
+            "refresh" => Ok(TaskTypes::REFRESH),
+            "cookie" => Ok(TaskTypes::COOKIE),
+            "save_cookies" => Ok(TaskTypes::SAVECOOKIES),
+            "load_cookies" => Ok(TaskTypes::LOADCOOKIES),
+            "local_storage" => Ok(TaskTypes::LOCALSTORAGE),
+            "session_storage" => Ok(TaskTypes::SESSIONSTORAGE),
             "validate" => Ok(TaskTypes::VALIDATE),
+            "validate_table" => Ok(TaskTypes::VALIDATETABLE),
             "set_vars" => Ok(TaskTypes::SETVARIABLE),
+            "execute_script" => Ok(TaskTypes::EXECUTESCRIPT),
             _ => Err(TaskErr {
                 message: format!("Unknow Task Type: {:#?}", input),
                 task: None,
@@ -94,6 +171,84 @@ pub struct ValidationResult {
     pub message: String,
 }
 
+/// Resolves the variables in the expected value and compares it to `actual`.
+fn check(
+    label: &str,
+    expect: &Matcher,
+    actual: &str,
+    web_driver_session: &WebDriverSession,
+) -> ValidationResult {
+    match web_driver_session.resolve(&expect.expected) {
+        Ok(expected) => match_result(label, &expect.with_expected(expected), actual),
+        Err(e) => ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: format!("Failed: {}", e),
+        },
+    }
+}
+
+fn match_result(label: &str, expect: &Matcher, actual: &str) -> ValidationResult {
+    match expect.is_match(actual) {
+        Ok(true) => ValidationResult {
+            validation: ValidationReultType::SUCCESS,
+            message: format!("Pass: {} {}", label, expect.pass_message()),
+        },
+        Ok(false) => ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: format!(
+                "Failed: {} expected: {}, actual: [{}]",
+                label,
+                expect.fail_message(),
+                actual
+            ),
+        },
+        Err(e) => ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: format!("Failed: {} {}", label, e),
+        },
+    }
+}
+
+fn get_on_fail(validate: &Mapping) -> Result<Option<OnFail>, String> {
+    let on_fail = match validate.get("on_fail") {
+        Some(on_fail) => on_fail,
+        None => return Ok(None),
+    };
+
+    match on_fail.as_str() {
+        Some(on_fail) => Ok(Some(OnFail::from_str(on_fail)?)),
+        None => Err("on_fail - value is not a string".to_string()),
+    }
+}
+
+/// Splits the named keys out of `input` and resolves the variables in the
+/// text, a variable named like a key wins over the key.
+fn resolve_keys(input: &str, web_driver_session: &WebDriverSession) -> Result<Vec<KeyInput>, String> {
+    let is_variable =
+        |name: &str| get_variable(&[name.to_string()], &web_driver_session.variables).is_some();
+    parse_keys(input, is_variable)?
+        .into_iter()
+        .map(|key| match key {
+            KeyInput::Text(text) => Ok(KeyInput::Text(web_driver_session.resolve(&text)?)),
+            chord => Ok(chord),
+        })
+        .collect()
+}
+
+/// Joins the failed validations into one message, `None` when all passed.
+fn failed_validations(results: &[ValidationResult]) -> Option<String> {
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|r| r.validation == ValidationReultType::FAILED)
+        .map(|r| r.message.as_str())
+        .collect();
+
+    match failed.is_empty() {
+        true => None,
+        false => Some(failed.join("\n")),
+    }
+}
+
 pub fn to_task(path: PathBuf) -> TaskResult<Tasks> {
     let mut tasks: Vec<Box<dyn Task>> = vec![];
     let task_data = get_task_data(path)?;
@@ -117,13 +272,34 @@ fn data_to_task(task_data: &HashMap<String, Value>) -> TaskResult<Box<dyn Task>>
     let task_type = get_task_type(task_data)?;
     let task: Box<dyn Task> = match task_type {
         TaskTypes::SENDKEY => Box::new(<SendKey as Task>::new(task_data)?),
+        TaskTypes::PRESSKEYS => Box::new(<PressKeys as Task>::new(task_data)?),
         TaskTypes::CLICK => Box::new(<Click as Task>::new(task_data)?),
         TaskTypes::CLOSE => Box::new(<Close as Task>::new(task_data)?),
         TaskTypes::LINK => Box::new(<Link as Task>::new(task_data)?),
         TaskTypes::WAIT => Box::new(<Wait as Task>::new(task_data)?),
         TaskTypes::SCREENSHOT => Box::new(<Screenshot as Task>::new(task_data)?),
+        TaskTypes::SELECT => Box::new(<Select as Task>::new(task_data)?),
+        TaskTypes::MOUSE => Box::new(<Mouse as Task>::new(task_data)?),
+        TaskTypes::SCROLL => Box::new(<Scroll as Task>::new(task_data)?),
+        TaskTypes::UPLOAD => Box::new(<Upload as Task>::new(task_data)?),
+        TaskTypes::SWITCHFRAME | TaskTypes::SWITCHTOPARENT | TaskTypes::DEFAULTCONTENT => {
+            Box::new(<SwitchFrame as Task>::new(task_data)?)
+        }
+        TaskTypes::WINDOW => Box::new(<Window as Task>::new(task_data)?),
+        TaskTypes::ALERT => Box::new(<Alert as Task>::new(task_data)?),
+        TaskTypes::BACK | TaskTypes::FORWARD | TaskTypes::REFRESH => {
+            Box::new(<Navigate as Task>::new(task_data)?)
+        }
+        TaskTypes::COOKIE | TaskTypes::SAVECOOKIES | TaskTypes::LOADCOOKIES => {
+            Box::new(<Cookies as Task>::new(task_data)?)
+        }
+        TaskTypes::LOCALSTORAGE | TaskTypes::SESSIONSTORAGE => {
+            Box::new(<Storage as Task>::new(task_data)?)
+        }
         TaskTypes::VALIDATE => Box::new(<Validate as Task>::new(task_data)?),
+        TaskTypes::VALIDATETABLE => Box::new(<ValidateTable as Task>::new(task_data)?),
         TaskTypes::SETVARIABLE => Box::new(<SetVars as Task>::new(task_data)?),
+        TaskTypes::EXECUTESCRIPT => Box::new(<ExecuteScript as Task>::new(task_data)?),
         _ => {
             return Err(TaskErr {
                 message: "Invalid Task Type".to_string(),
@@ -310,6 +486,8 @@ pub struct TaskOk {
     pub task_type: TaskTypes,
     pub duration: u64,
     pub result: Option<Vec<ValidationResult>>,
+    /// File written by the task, like a screenshot.
+    pub path: Option<String>,
 }
 
 impl fmt::Display for TaskOk {
@@ -345,6 +523,10 @@ impl TaskErr {
     pub fn get_message(&self) -> &str {
         &self.message
     }
+
+    pub fn get_task_type(&self) -> Option<TaskTypes> {
+        self.task_type
+    }
 }
 
 impl fmt::Display for TaskErr {
@@ -361,26 +543,6 @@ impl fmt::Display for TaskErr {
     }
 }
 
-fn to_hash(task_data: &Mapping) -> Result<HashMap<String, String>, String> {
-    let mut task_hash: HashMap<String, String> = HashMap::new();
-
-    for (key, value) in task_data {
-        let key = match key.as_str() {
-            None => return Err(format!("Key: {:?} is not a string", key)),
-            Some(k) => k.to_owned(),
-        };
-
-        let value = match value.as_str() {
-            None => return Err(format!("Value: {:?} is not a string", value)),
-            Some(v) => v.to_owned(),
-        };
-
-        task_hash.insert(key, value);
-    }
-
-    Ok(task_hash)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -630,4 +792,22 @@ tasks:
         });
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn test_failed_validations() {
+        let pass = ValidationResult {
+            validation: ValidationReultType::SUCCESS,
+            message: "Pass: Text is Rust".to_string(),
+        };
+        let failed = ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: "Failed: Title expected: [Rust], actual: [Go]".to_string(),
+        };
+
+        assert_eq!(None, failed_validations(&[pass.clone(), pass.clone()]));
+        assert_eq!(
+            Some("Failed: Title expected: [Rust], actual: [Go]".to_string()),
+            failed_validations(&[pass, failed])
+        );
+    }
 }
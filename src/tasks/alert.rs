@@ -0,0 +1,260 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Instant;
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    executor::{ExecuteResult, WebDriverSession},
+    variables::Variable,
+};
+
+const TASK_TYPE: &str = "alert";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlertAction {
+    Accept,
+    Dismiss,
+    Read,
+    SendText,
+}
+
+impl FromStr for AlertAction {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<AlertAction, Self::Err> {
+        match input {
+            "accept" => Ok(AlertAction::Accept),
+            "dismiss" => Ok(AlertAction::Dismiss),
+            "read" => Ok(AlertAction::Read),
+            "send_text" => Ok(AlertAction::SendText),
+            _ => Err(format!("Unknown alert action: {}", input)),
+        }
+    }
+}
+
+/// Handles the open `alert`, `confirm` or `prompt` dialog.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Alert {
+    _task_types: TaskTypes,
+    name: String,
+    action: AlertAction,
+    /// Typed into a prompt by `send_text`.
+    text: Option<String>,
+    /// Variable getting the dialog message, read before the action.
+    save_as: Option<String>,
+}
+
+#[async_trait]
+impl Task for Alert {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Alert> {
+        let name = get_task_name(task)?;
+        let alert = get_task(task, TASK_TYPE)?;
+
+        match get_alert(name, alert) {
+            Ok(alert) => Ok(alert),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::ALERT),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, mut web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.handle(&mut web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(TaskTypes::ALERT),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::ALERT,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Alert {
+    async fn handle(&self, web_driver_session: &mut WebDriverSession) -> Result<(), String> {
+        let driver = &web_driver_session.driver;
+
+        if let Some(save_as) = &self.save_as {
+            let text = match driver.get_alert_text().await {
+                Ok(text) => text,
+                Err(e) => return Err(format!("No alert open: {}", e)),
+            };
+            web_driver_session.add_variable(save_as, Variable::from(text))?;
+        }
+
+        let driver = &web_driver_session.driver;
+        let result = match (self.action, &self.text) {
+            (AlertAction::Accept, _) => driver.accept_alert().await,
+            (AlertAction::Dismiss, _) => driver.dismiss_alert().await,
+            (AlertAction::Read, _) => Ok(()),
+            (AlertAction::SendText, Some(text)) => {
+                let text = web_driver_session.resolve(text)?;
+                driver.send_alert_text(text).await
+            }
+            (AlertAction::SendText, None) => return Err("text field not found".to_string()),
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Unable to handle alert: {}", e)),
+        }
+    }
+}
+
+fn get_alert(name: String, alert: &Mapping) -> Result<Alert, String> {
+    let action = match alert.get("action").map(|action| action.as_str()) {
+        Some(Some(action)) => AlertAction::from_str(action)?,
+        Some(None) => return Err("action field is not a string".to_string()),
+        None => return Err("action field not found".to_string()),
+    };
+
+    let text = match alert.get("text") {
+        None => None,
+        Some(text) => match text.as_str() {
+            Some(text) => Some(text.to_string()),
+            None => return Err("text field is not a string".to_string()),
+        },
+    };
+
+    let save_as = match alert.get("save_as") {
+        None => None,
+        Some(save_as) => match save_as.as_str() {
+            Some(save_as) => Some(save_as.to_string()),
+            None => return Err("save_as field is not a string".to_string()),
+        },
+    };
+
+    match (action, &text, &save_as) {
+        (AlertAction::SendText, None, _) => {
+            return Err("send_text - text field not found".to_string())
+        }
+        (AlertAction::Read, _, None) => return Err("read - save_as field not found".to_string()),
+        (AlertAction::SendText, Some(_), _) | (_, None, _) => {}
+        _ => return Err("text is only used by send_text".to_string()),
+    }
+
+    Ok(Alert {
+        _task_types: TaskTypes::ALERT,
+        name,
+        action,
+        text,
+        save_as,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(yaml: &str) -> Result<Alert, String> {
+        let task = serde_yaml::from_str(yaml).unwrap();
+        Alert::new(&task).map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Alert::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'confirm the delete'
+        alert:
+            action: accept
+            save_as: 'confirm_message'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Alert::new(&task);
+        let expected = Ok(Alert {
+            _task_types: TaskTypes::ALERT,
+            name: "confirm the delete".to_owned(),
+            action: AlertAction::Accept,
+            text: None,
+            save_as: Some("confirm_message".to_owned()),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_send_text() {
+        let yaml = "
+        name: 'answer the prompt'
+        alert:
+            action: send_text
+            text: '{user}'
+              ";
+
+        let result = alert(yaml).unwrap();
+        assert_eq!(AlertAction::SendText, result.action);
+        assert_eq!(Some("{user}".to_owned()), result.text);
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+        name: 'answer the prompt'
+        alert:
+            action: send_text
+              ";
+        let expected = Err("send_text - text field not found".to_string());
+        assert_eq!(expected, alert(yaml));
+
+        let yaml = "
+        name: 'read the alert'
+        alert:
+            action: read
+              ";
+        let expected = Err("read - save_as field not found".to_string());
+        assert_eq!(expected, alert(yaml));
+
+        let yaml = "
+        name: 'accept the alert'
+        alert:
+            action: accept
+            text: 'yes'
+              ";
+        let expected = Err("text is only used by send_text".to_string());
+        assert_eq!(expected, alert(yaml));
+
+        let yaml = "
+        name: 'close the alert'
+        alert:
+            action: close
+              ";
+        let expected = Err("Unknown alert action: close".to_string());
+        assert_eq!(expected, alert(yaml));
+    }
+}
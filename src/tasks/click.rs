@@ -43,6 +43,14 @@ impl Task for Click {
         })
     }
 
+    fn element(&self) -> Option<&Element> {
+        Some(&self.element)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
         // println!(
@@ -50,7 +58,19 @@ impl Task for Click {
         //     self._task_types, self.name, self.element.element_type, self.element.value
         // );
 
-        let by: By = Element::find_by_resolve(&self.element, &web_driver_session.variables);
+        let by: By = match Element::find_by_resolve(&self.element, &web_driver_session) {
+            Ok(by) => by,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::CLICK),
+                    },
+                ));
+            }
+        };
 
         let element = match web_driver_session.driver.find(by).await {
             Ok(element) => element,
@@ -77,6 +97,7 @@ impl Task for Click {
                     task_type: TaskTypes::CLICK,
                     duration: start.elapsed().as_secs(),
                     result: None,
+                    path: None,
                 },
             )),
             Err(e) => {
@@ -258,6 +279,7 @@ mod tests {
             element: Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
+                frame: None,
             },
         });
         assert_eq!(expected, result)
@@ -280,6 +302,7 @@ mod tests {
             element: Element {
                 element_type: ElementType::ID,
                 value: "search-form".to_owned(),
+                frame: None,
             },
         });
         assert_eq!(expected, result)
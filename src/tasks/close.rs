@@ -31,6 +31,10 @@ impl Task for Close {
         })
     }
 
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
         // println!(
@@ -49,7 +53,8 @@ impl Task for Close {
                         name,
                         task_type: TaskTypes::CLOSE,
                         duration: start.elapsed().as_secs(),
-                        result: None
+                        result: None,
+                        path: None
                     },
                 ));
             }
@@ -0,0 +1,515 @@
+use async_trait::async_trait;
+use cookie::{time::OffsetDateTime, SameSite};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use thirtyfour::Cookie;
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    executor::{ExecuteResult, WebDriverSession},
+    variables::Variable,
+};
+
+const COOKIE: &str = "cookie";
+const SAVE_COOKIES: &str = "save_cookies";
+const LOAD_COOKIES: &str = "load_cookies";
+
+/// A cookie as the WebDriver protocol writes it, also the format of the
+/// `save_cookies` files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredCookie {
+    name: String,
+    value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    domain: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secure: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    http_only: Option<bool>,
+    /// Seconds since the epoch, session cookies have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expiry: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    same_site: Option<String>,
+}
+
+impl StoredCookie {
+    fn from_cookie(cookie: &Cookie) -> Self {
+        StoredCookie {
+            name: cookie.name().to_string(),
+            value: cookie.value().to_string(),
+            domain: cookie.domain().map(String::from),
+            path: cookie.path().map(String::from),
+            secure: cookie.secure(),
+            http_only: cookie.http_only(),
+            expiry: cookie
+                .expires()
+                .and_then(|expires| expires.datetime())
+                .map(|expires| expires.unix_timestamp()),
+            same_site: cookie.same_site().map(|same_site| same_site.to_string()),
+        }
+    }
+
+    fn to_cookie(&self) -> Result<Cookie<'static>, String> {
+        let mut cookie = Cookie::new(self.name.clone(), self.value.clone());
+        if let Some(domain) = &self.domain {
+            cookie.set_domain(domain.clone());
+        }
+        if let Some(path) = &self.path {
+            cookie.set_path(path.clone());
+        }
+        if let Some(secure) = self.secure {
+            cookie.set_secure(secure);
+        }
+        if let Some(http_only) = self.http_only {
+            cookie.set_http_only(http_only);
+        }
+        if let Some(expiry) = self.expiry {
+            match OffsetDateTime::from_unix_timestamp(expiry) {
+                Ok(expiry) => cookie.set_expires(expiry),
+                Err(_) => return Err(format!("expiry: [{}] is not a timestamp", expiry)),
+            }
+        }
+        if let Some(same_site) = &self.same_site {
+            cookie.set_same_site(match same_site.to_lowercase().as_str() {
+                "strict" => SameSite::Strict,
+                "lax" => SameSite::Lax,
+                "none" => SameSite::None,
+                _ => {
+                    return Err(format!(
+                        "same_site: [{}] expected strict, lax or none",
+                        same_site
+                    ))
+                }
+            });
+        }
+        Ok(cookie)
+    }
+
+    fn is_expired(&self, now: i64) -> bool {
+        matches!(self.expiry, Some(expiry) if expiry <= now)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum CookieAction {
+    Add(StoredCookie),
+    Get(String, String),
+    Delete(String),
+    Clear,
+    Save(String),
+    Load(String),
+}
+
+/// Works on the cookies of the current page, `save_cookies` and
+/// `load_cookies` keep them between runs, like a login.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Cookies {
+    _task_types: TaskTypes,
+    name: String,
+    action: CookieAction,
+}
+
+#[async_trait]
+impl Task for Cookies {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Cookies> {
+        let name = get_task_name(task)?;
+
+        let (task_type, action) = if let Some(path) = task.get(SAVE_COOKIES) {
+            (
+                TaskTypes::SAVECOOKIES,
+                get_path(path).map(CookieAction::Save),
+            )
+        } else if let Some(path) = task.get(LOAD_COOKIES) {
+            (
+                TaskTypes::LOADCOOKIES,
+                get_path(path).map(CookieAction::Load),
+            )
+        } else {
+            let cookie = get_task(task, COOKIE)?;
+            (TaskTypes::COOKIE, get_action(cookie))
+        };
+
+        match action {
+            Ok(action) => Ok(Cookies {
+                _task_types: task_type,
+                name,
+                action,
+            }),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(task_type),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, mut web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        let path = match self.perform(&mut web_driver_session).await {
+            Ok(path) => path,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(self._task_types),
+                    },
+                ))
+            }
+        };
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: self._task_types,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path,
+            },
+        ))
+    }
+}
+
+impl Cookies {
+    /// Runs the action, returning the file written by `save_cookies`.
+    async fn perform(
+        &self,
+        web_driver_session: &mut WebDriverSession,
+    ) -> Result<Option<String>, String> {
+        let driver = &web_driver_session.driver;
+
+        match &self.action {
+            CookieAction::Add(cookie) => {
+                let cookie = StoredCookie {
+                    name: web_driver_session.resolve(&cookie.name)?,
+                    value: web_driver_session.resolve(&cookie.value)?,
+                    ..cookie.clone()
+                };
+                add_cookie(&cookie, web_driver_session).await?;
+            }
+            CookieAction::Get(name, save_as) => {
+                let name = web_driver_session.resolve(name)?;
+                // The driver reports a missing cookie like any other error,
+                // reading them all tells the two apart.
+                let cookies = match driver.get_all_cookies().await {
+                    Ok(cookies) => cookies,
+                    Err(e) => return Err(format!("Unable to read cookies: {}", e)),
+                };
+                let value = match cookies.iter().find(|cookie| cookie.name() == name) {
+                    Some(cookie) => cookie.value().to_string(),
+                    None => return Err(format!("Cookie [{}] not found", name)),
+                };
+                web_driver_session.add_variable(save_as, Variable::from(value))?;
+            }
+            CookieAction::Delete(name) => {
+                let name = web_driver_session.resolve(name)?;
+                if let Err(e) = driver.delete_cookie(&name).await {
+                    return Err(format!("Unable to delete cookie [{}]: {}", name, e));
+                }
+            }
+            CookieAction::Clear => {
+                if let Err(e) = driver.delete_all_cookies().await {
+                    return Err(format!("Unable to clear cookies: {}", e));
+                }
+            }
+            CookieAction::Save(path) => {
+                let path = web_driver_session.resolve(path)?;
+                let cookies = match driver.get_all_cookies().await {
+                    Ok(cookies) => cookies,
+                    Err(e) => return Err(format!("Unable to read cookies: {}", e)),
+                };
+                let cookies: Vec<StoredCookie> =
+                    cookies.iter().map(StoredCookie::from_cookie).collect();
+                save_cookies(&path, &cookies)?;
+                return Ok(Some(path));
+            }
+            CookieAction::Load(path) => {
+                let path = web_driver_session.resolve(path)?;
+                let now = OffsetDateTime::now_utc().unix_timestamp();
+                // An expired login is dropped, the run then logs in again.
+                for cookie in load_cookies(&path)? {
+                    if !cookie.is_expired(now) {
+                        add_cookie(&cookie, web_driver_session).await?;
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+async fn add_cookie(
+    cookie: &StoredCookie,
+    web_driver_session: &WebDriverSession,
+) -> Result<(), String> {
+    match web_driver_session
+        .driver
+        .add_cookie(cookie.to_cookie()?)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Unable to add cookie [{}]: {}", cookie.name, e)),
+    }
+}
+
+fn save_cookies(path: &str, cookies: &[StoredCookie]) -> Result<(), String> {
+    if let Some(parent) = Path::new(path).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            return Err(format!(
+                "Unable to create directory [{}]: {}",
+                parent.display(),
+                e
+            ));
+        }
+    }
+
+    let json = match serde_json::to_string_pretty(cookies) {
+        Ok(json) => json,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    match fs::write(path, json) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Unable to save cookies to [{}]: {}", path, e)),
+    }
+}
+
+fn load_cookies(path: &str) -> Result<Vec<StoredCookie>, String> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) => return Err(format!("Unable to load cookies from [{}]: {}", path, e)),
+    };
+
+    match serde_json::from_str(&json) {
+        Ok(cookies) => Ok(cookies),
+        Err(e) => Err(format!("Cookies file [{}] is not valid: {}", path, e)),
+    }
+}
+
+fn get_path(path: &Value) -> Result<String, String> {
+    match path.as_str() {
+        Some("") => Err("path is empty".to_string()),
+        Some(path) => Ok(path.to_string()),
+        None => Err("path is not a string".to_string()),
+    }
+}
+
+fn get_action(cookie: &Mapping) -> Result<CookieAction, String> {
+    let action = match cookie.get("action").map(|action| action.as_str()) {
+        Some(Some(action)) => action,
+        Some(None) => return Err("action field is not a string".to_string()),
+        None => return Err("action field not found".to_string()),
+    };
+
+    match action {
+        "add" => {
+            let mut stored = StoredCookie {
+                name: get_string(cookie, "name")?,
+                value: get_string(cookie, "value")?,
+                ..StoredCookie::default()
+            };
+            stored.domain = get_optional_string(cookie, "domain")?;
+            stored.path = get_optional_string(cookie, "path")?;
+            stored.secure = get_optional_bool(cookie, "secure")?;
+            stored.http_only = get_optional_bool(cookie, "http_only")?;
+            stored.expiry = match cookie.get("expiry") {
+                None => None,
+                Some(expiry) => match expiry.as_i64() {
+                    Some(expiry) => Some(expiry),
+                    None => return Err("expiry field is not a number".to_string()),
+                },
+            };
+            stored.same_site = get_optional_string(cookie, "same_site")?;
+            // Checks same_site and expiry now rather than at runtime.
+            stored.to_cookie()?;
+            Ok(CookieAction::Add(stored))
+        }
+        "get" => Ok(CookieAction::Get(
+            get_string(cookie, "name")?,
+            get_string(cookie, "save_as")?,
+        )),
+        "delete" => Ok(CookieAction::Delete(get_string(cookie, "name")?)),
+        "clear" => Ok(CookieAction::Clear),
+        _ => Err(format!("Unknown cookie action: {}", action)),
+    }
+}
+
+fn get_string(cookie: &Mapping, key: &str) -> Result<String, String> {
+    match get_optional_string(cookie, key)? {
+        Some(value) => Ok(value),
+        None => Err(format!("{} field not found", key)),
+    }
+}
+
+fn get_optional_string(cookie: &Mapping, key: &str) -> Result<Option<String>, String> {
+    match cookie.get(key) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(format!("{} field is not a string", key)),
+    }
+}
+
+fn get_optional_bool(cookie: &Mapping, key: &str) -> Result<Option<bool>, String> {
+    match cookie.get(key) {
+        None => Ok(None),
+        Some(value) => match value.as_bool() {
+            Some(value) => Ok(Some(value)),
+            None => Err(format!("{} field is not a bool", key)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(name: &str, value: &str) -> StoredCookie {
+        StoredCookie {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            ..StoredCookie::default()
+        }
+    }
+
+    fn action(yaml: &str) -> Result<CookieAction, String> {
+        let task = serde_yaml::from_str(yaml).unwrap();
+        Cookies::new(&task)
+            .map(|cookies| cookies.action)
+            .map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Cookies::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_add() {
+        let yaml = "
+        name: 'accept the cookie banner'
+        cookie:
+            action: add
+            name: 'consent'
+            value: 'yes'
+            path: '/'
+            secure: true
+            same_site: 'Lax'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Cookies::new(&task);
+        let expected = Ok(Cookies {
+            _task_types: TaskTypes::COOKIE,
+            name: "accept the cookie banner".to_owned(),
+            action: CookieAction::Add(StoredCookie {
+                path: Some("/".to_owned()),
+                secure: Some(true),
+                same_site: Some("Lax".to_owned()),
+                ..stored("consent", "yes")
+            }),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_get_and_files() {
+        let yaml = "
+        name: 'read the session'
+        cookie:
+            action: get
+            name: 'session'
+            save_as: 'session_id'
+              ";
+        let expected = Ok(CookieAction::Get(
+            "session".to_owned(),
+            "session_id".to_owned(),
+        ));
+        assert_eq!(expected, action(yaml));
+
+        let yaml = "
+        name: 'keep the login'
+        save_cookies: 'cookies/login.json'
+              ";
+        let expected = Ok(CookieAction::Save("cookies/login.json".to_owned()));
+        assert_eq!(expected, action(yaml));
+
+        let yaml = "
+        name: 'reuse the login'
+        load_cookies: 'cookies/login.json'
+              ";
+        let expected = Ok(CookieAction::Load("cookies/login.json".to_owned()));
+        assert_eq!(expected, action(yaml));
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+        name: 'accept the cookie banner'
+        cookie:
+            action: add
+            name: 'consent'
+            value: 'yes'
+            same_site: 'always'
+              ";
+        let expected = Err("same_site: [always] expected strict, lax or none".to_string());
+        assert_eq!(expected, action(yaml));
+
+        let yaml = "
+        name: 'read the session'
+        cookie:
+            action: get
+            name: 'session'
+              ";
+        let expected = Err("save_as field not found".to_string());
+        assert_eq!(expected, action(yaml));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("ls-cookies-{}", std::process::id()));
+        let path = dir.join("login.json");
+        let path = path.to_str().unwrap();
+
+        let cookie = StoredCookie {
+            domain: Some("example.com".to_owned()),
+            http_only: Some(true),
+            expiry: Some(1700000000),
+            ..stored("session", "abc")
+        };
+        save_cookies(path, std::slice::from_ref(&cookie)).unwrap();
+
+        let json = fs::read_to_string(path).unwrap();
+        assert!(json.contains("\"httpOnly\": true"));
+        assert_eq!(Ok(vec![cookie.clone()]), load_cookies(path));
+        assert!(cookie.is_expired(1700000000));
+        assert!(!cookie.is_expired(1600000000));
+
+        let stored = StoredCookie::from_cookie(&cookie.to_cookie().unwrap());
+        assert_eq!(cookie, stored);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::time::Instant;
+use thirtyfour::By;
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    element::Element,
+    executor::{ExecuteResult, WebDriverSession},
+    variables::Variable,
+};
+
+const TASK_TYPE: &str = "execute_script";
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct ExecuteScript {
+    _task_types: TaskTypes,
+    name: String,
+    script: String,
+    /// `resolve: false` sends the script as written, for JavaScript blocks
+    /// like `{x}` that look like variables.
+    resolve: bool,
+    is_async: bool,
+    elements: Vec<Element>,
+    save_as: Option<String>,
+}
+
+#[async_trait]
+impl Task for ExecuteScript {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<ExecuteScript> {
+        let name = get_task_name(task)?;
+        let execute_script = get_task(task, TASK_TYPE)?;
+
+        match get_execute_script(name, execute_script) {
+            Ok(execute_script) => Ok(execute_script),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::EXECUTESCRIPT),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, mut web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        let result = match self.run(&web_driver_session).await {
+            Ok(result) => result,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::EXECUTESCRIPT),
+                    },
+                ))
+            }
+        };
+
+        if let Some(save_as) = &self.save_as {
+            if let Err(e) = web_driver_session.add_variable(save_as, Variable::from(result)) {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::EXECUTESCRIPT),
+                    },
+                ));
+            }
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::EXECUTESCRIPT,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl ExecuteScript {
+    async fn run(
+        &self,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<serde_json::Value, String> {
+        let script = match self.resolve {
+            true => web_driver_session.resolve(&self.script)?,
+            false => self.script.clone(),
+        };
+        let driver = &web_driver_session.driver;
+
+        let mut args: Vec<serde_json::Value> = Vec::new();
+        for element in &self.elements {
+            let by: By = Element::find_by_resolve(element, web_driver_session)?;
+            let element = match driver.find(by).await {
+                Ok(element) => element,
+                Err(e) => return Err(format!("{}", e)),
+            };
+            match element.to_json() {
+                Ok(element) => args.push(element),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+
+        let ret = match self.is_async {
+            true => driver.execute_async(&script, args).await,
+            false => driver.execute(&script, args).await,
+        };
+
+        match ret {
+            Ok(ret) => Ok(ret.json().clone()),
+            Err(e) => Err(format!("Script failed: {}", e)),
+        }
+    }
+}
+
+fn get_execute_script(name: String, execute_script: &Mapping) -> Result<ExecuteScript, String> {
+    let script = match execute_script.get("script") {
+        Some(script) => match script.as_str() {
+            Some("") => return Err("script is empty".to_string()),
+            Some(script) => script.to_string(),
+            None => return Err("script is not a string".to_string()),
+        },
+        None => return Err("script field not found".to_string()),
+    };
+
+    let resolve = match execute_script.get("resolve") {
+        None => true,
+        Some(resolve) => match resolve.as_bool() {
+            Some(resolve) => resolve,
+            None => return Err("resolve field is not a bool".to_string()),
+        },
+    };
+
+    let is_async = match execute_script.get("async") {
+        None => false,
+        Some(is_async) => match is_async.as_bool() {
+            Some(is_async) => is_async,
+            None => return Err("async field is not a bool".to_string()),
+        },
+    };
+
+    let elements = match execute_script.get("args") {
+        None => Vec::new(),
+        Some(args) => match args.as_sequence() {
+            Some(args) => get_elements(args)?,
+            None => return Err("args field is not a list".to_string()),
+        },
+    };
+
+    let save_as = match execute_script.get("save_as") {
+        None => None,
+        Some(save_as) => match save_as.as_str() {
+            Some(save_as) => Some(save_as.to_string()),
+            None => return Err("save_as field is not a string".to_string()),
+        },
+    };
+
+    Ok(ExecuteScript {
+        _task_types: TaskTypes::EXECUTESCRIPT,
+        name,
+        script,
+        resolve,
+        is_async,
+        elements,
+        save_as,
+    })
+}
+
+fn get_elements(args: &[Value]) -> Result<Vec<Element>, String> {
+    let mut elements: Vec<Element> = Vec::new();
+    for locator in args {
+        match locator.as_mapping() {
+            Some(locator) => elements.push(Element::from_locator(locator)?),
+            None => return Err("args: locator is not a map".to_string()),
+        }
+    }
+    Ok(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = ExecuteScript::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'read the app state'
+        execute_script:
+            script: 'return window.__APP_STATE__.user;'
+            save_as: 'user'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = ExecuteScript::new(&task);
+        let expected = Ok(ExecuteScript {
+            _task_types: TaskTypes::EXECUTESCRIPT,
+            name: "read the app state".to_owned(),
+            script: "return window.__APP_STATE__.user;".to_owned(),
+            resolve: true,
+            is_async: false,
+            elements: Vec::new(),
+            save_as: Some("user".to_owned()),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_async_with_args() {
+        let yaml = "
+        name: 'scroll the list'
+        execute_script:
+            script: 'arguments[0].scrollTop = 500; arguments[1]();'
+            async: true
+            args:
+                - id: 'list'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = ExecuteScript::new(&task);
+        let expected = Ok(ExecuteScript {
+            _task_types: TaskTypes::EXECUTESCRIPT,
+            name: "scroll the list".to_owned(),
+            script: "arguments[0].scrollTop = 500; arguments[1]();".to_owned(),
+            resolve: true,
+            is_async: true,
+            elements: vec![Element {
+                element_type: ElementType::ID,
+                value: "list".to_owned(),
+                frame: None,
+            }],
+            save_as: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_resolve() {
+        let yaml = "
+        name: 'open the item'
+        execute_script:
+            script: \"window.app.open('{item_id}');\"
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = ExecuteScript::new(&task).unwrap();
+        assert!(result.resolve);
+
+        let yaml = "
+        name: 'toggle the menu'
+        execute_script:
+            script: 'if (open) { menu.close(); }'
+            resolve: false
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = ExecuteScript::new(&task).unwrap();
+        assert!(!result.resolve);
+
+        let yaml = "
+        name: 'open the item'
+        execute_script:
+            script: 'if (open) { app.open(); }'
+            resolve: 'yes'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = ExecuteScript::new(&task).map_err(|e| e.message);
+        assert_eq!(Err("resolve field is not a bool".to_string()), result);
+    }
+
+    #[test]
+    fn test_missing_script() {
+        let yaml = "
+        name: 'read the app state'
+        execute_script:
+            save_as: 'user'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = ExecuteScript::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("script field not found"),
+            task: Some(task),
+            task_type: Some(TaskTypes::EXECUTESCRIPT),
+        });
+        assert_eq!(expected, result)
+    }
+}
@@ -3,7 +3,7 @@ use serde_yaml::Value;
 use std::collections::HashMap;
 use std::time::Instant;
 
-use crate::{executor::{ExecuteResult, WebDriverSession}, variables::resolve_variables};
+use crate::executor::{ExecuteResult, WebDriverSession};
 
 use super::{
     get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes,
@@ -30,6 +30,10 @@ impl Task for Link {
         })
     }
 
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
         // println!(
@@ -37,7 +41,19 @@ impl Task for Link {
         //     self._task_types, self.name, self.url
         // );
 
-        let url = resolve_variables(&self.url, &web_driver_session.variables);
+        let url = match web_driver_session.resolve(&self.url) {
+            Ok(url) => url,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::LINK),
+                    },
+                ))
+            }
+        };
 
         let link = web_driver_session.driver.goto(url).await;
         let name = self.name.clone();
@@ -49,7 +65,8 @@ impl Task for Link {
                     name,
                     task_type: TaskTypes::LINK,
                     duration: start.elapsed().as_secs(),
-                    result: None
+                    result: None,
+                    path: None
                 },
             )),
             Err(_) => {
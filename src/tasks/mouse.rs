@@ -0,0 +1,387 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Instant;
+use thirtyfour::{By, WebElement};
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    element::Element,
+    executor::{ExecuteResult, WebDriverSession},
+};
+
+const TASK_TYPE: &str = "mouse";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MouseAction {
+    Hover,
+    DoubleClick,
+    ContextClick,
+    ClickAndHold,
+    Release,
+    DragAndDrop,
+    ClickAt,
+}
+
+impl FromStr for MouseAction {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<MouseAction, Self::Err> {
+        match input {
+            "hover" => Ok(MouseAction::Hover),
+            "double_click" => Ok(MouseAction::DoubleClick),
+            "context_click" => Ok(MouseAction::ContextClick),
+            "click_and_hold" => Ok(MouseAction::ClickAndHold),
+            "release" => Ok(MouseAction::Release),
+            "drag_and_drop" => Ok(MouseAction::DragAndDrop),
+            "click_at" => Ok(MouseAction::ClickAt),
+            _ => Err(format!("Unknown mouse action: {}", input)),
+        }
+    }
+}
+
+/// Where a drag ends, on another element or moved by an offset.
+#[derive(Debug, PartialEq, Eq)]
+enum Target {
+    Element(Element),
+    Offset(i64, i64),
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Mouse {
+    _task_types: TaskTypes,
+    name: String,
+    action: MouseAction,
+    element: Option<Element>,
+    target: Option<Target>,
+}
+
+#[async_trait]
+impl Task for Mouse {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Mouse> {
+        let name = get_task_name(task)?;
+        let mouse = get_task(task, TASK_TYPE)?;
+
+        match get_mouse(name, mouse) {
+            Ok(mouse) => Ok(mouse),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::MOUSE),
+            }),
+        }
+    }
+
+    fn element(&self) -> Option<&Element> {
+        self.element.as_ref()
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.perform(&web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(TaskTypes::MOUSE),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::MOUSE,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Mouse {
+    async fn perform(&self, web_driver_session: &WebDriverSession) -> Result<(), String> {
+        let actions = web_driver_session.driver.action_chain();
+
+        let element = match &self.element {
+            Some(element) => Some(find(element, web_driver_session).await?),
+            None => None,
+        };
+
+        let actions = match (self.action, &element) {
+            (MouseAction::Release, Some(element)) => actions.release_on_element(element),
+            (MouseAction::Release, None) => actions.release(),
+            (_, None) => return Err("element field not found".to_string()),
+            (MouseAction::Hover, Some(element)) => actions.move_to_element_center(element),
+            (MouseAction::DoubleClick, Some(element)) => actions.double_click_element(element),
+            (MouseAction::ContextClick, Some(element)) => actions.context_click_element(element),
+            (MouseAction::ClickAndHold, Some(element)) => actions.click_and_hold_element(element),
+            (MouseAction::ClickAt, Some(element)) => {
+                let (x, y) = match self.target {
+                    Some(Target::Offset(x, y)) => (x, y),
+                    _ => (0, 0),
+                };
+                actions.move_to_element_with_offset(element, x, y).click()
+            }
+            // Moving before the release, a plain click_and_hold + release
+            // does not drop in most browsers.
+            (MouseAction::DragAndDrop, Some(element)) => match &self.target {
+                Some(Target::Element(target)) => {
+                    let target = find(target, web_driver_session).await?;
+                    actions
+                        .click_and_hold_element(element)
+                        .move_to_element_center(&target)
+                        .release()
+                }
+                Some(Target::Offset(x, y)) => actions
+                    .click_and_hold_element(element)
+                    .move_by_offset(*x, *y)
+                    .release(),
+                None => return Err("target or offset field not found".to_string()),
+            },
+        };
+
+        match actions.perform().await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Unable to perform mouse action: {}", e)),
+        }
+    }
+}
+
+async fn find(
+    element: &Element,
+    web_driver_session: &WebDriverSession,
+) -> Result<WebElement, String> {
+    let by: By = Element::find_by_resolve(element, web_driver_session)?;
+    match web_driver_session.driver.find(by).await {
+        Ok(element) => Ok(element),
+        Err(e) => Err(format!("{}", e)),
+    }
+}
+
+fn get_mouse(name: String, mouse: &Mapping) -> Result<Mouse, String> {
+    let action = match mouse.get("action") {
+        Some(action) => match action.as_str() {
+            Some(action) => MouseAction::from_str(action)?,
+            None => return Err("action field is not a string".to_string()),
+        },
+        None => return Err("action field not found".to_string()),
+    };
+
+    let element = match mouse.get("element") {
+        Some(_) => Some(Element::new(mouse)?),
+        None if action == MouseAction::Release => None,
+        None => return Err("element field not found".to_string()),
+    };
+
+    let target = match (mouse.get("target"), get_offset(mouse)?) {
+        (Some(_), Some(_)) => return Err("Only one of target or offset is allowed".to_string()),
+        (Some(target), None) => match target.as_mapping() {
+            Some(target) => Some(Target::Element(Element::from_locator(target)?)),
+            None => return Err("target field is not a map".to_string()),
+        },
+        (None, Some((x, y))) => Some(Target::Offset(x, y)),
+        (None, None) => None,
+    };
+
+    match (action, &target) {
+        (MouseAction::DragAndDrop, None) => {
+            return Err("drag_and_drop - expected a target or an offset".to_string())
+        }
+        (MouseAction::DragAndDrop, _) | (_, None) => {}
+        (MouseAction::ClickAt, Some(Target::Offset(_, _))) => {}
+        (MouseAction::ClickAt, Some(_)) => {
+            return Err("click_at - expected an offset, not a target".to_string())
+        }
+        (_, Some(_)) => {
+            return Err("target and offset are only used by drag_and_drop and click_at".to_string())
+        }
+    }
+
+    Ok(Mouse {
+        _task_types: TaskTypes::MOUSE,
+        name,
+        action,
+        element,
+        target,
+    })
+}
+
+fn get_offset(mouse: &Mapping) -> Result<Option<(i64, i64)>, String> {
+    let offset = match mouse.get("offset") {
+        Some(offset) => match offset.as_mapping() {
+            Some(offset) => offset,
+            None => return Err("offset field is not a map".to_string()),
+        },
+        None => return Ok(None),
+    };
+
+    let axis = |key: &str| match offset.get(key) {
+        None => Ok(0),
+        Some(value) => match value.as_i64() {
+            Some(value) => Ok(value),
+            None => Err(format!("offset - {} is not a number", key)),
+        },
+    };
+
+    Ok(Some((axis("x")?, axis("y")?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    fn mouse(yaml: &str) -> TaskResult<Mouse> {
+        let task = serde_yaml::from_str(yaml).unwrap();
+        Mouse::new(&task)
+    }
+
+    fn id(value: &str) -> Element {
+        Element {
+            element_type: ElementType::ID,
+            value: value.to_owned(),
+            frame: None,
+        }
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Mouse::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_hover() {
+        let yaml = "
+                name: 'Open the menu'
+                mouse:
+                    action: 'hover'
+                    element:
+                        id: 'menu'
+              ";
+
+        let expected = Ok(Mouse {
+            _task_types: TaskTypes::MOUSE,
+            name: "Open the menu".to_owned(),
+            action: MouseAction::Hover,
+            element: Some(id("menu")),
+            target: None,
+        });
+        assert_eq!(expected, mouse(yaml))
+    }
+
+    #[test]
+    fn test_task_drag_and_drop() {
+        let yaml = "
+                name: 'Move the card'
+                mouse:
+                    action: 'drag_and_drop'
+                    element:
+                        id: 'card'
+                    target:
+                        id: 'done'
+              ";
+        assert_eq!(
+            Some(Target::Element(id("done"))),
+            mouse(yaml).unwrap().target
+        );
+
+        let yaml = "
+                name: 'Move the card'
+                mouse:
+                    action: 'drag_and_drop'
+                    element:
+                        id: 'card'
+                    offset:
+                        x: 200
+              ";
+        assert_eq!(Some(Target::Offset(200, 0)), mouse(yaml).unwrap().target);
+    }
+
+    #[test]
+    fn test_task_target_in_frame() {
+        let yaml = "
+                name: 'Move the card'
+                mouse:
+                    action: 'drag_and_drop'
+                    element:
+                        id: 'card'
+                    target:
+                        id: 'done'
+                        frame:
+                            id: 'board'
+              ";
+        assert_eq!(
+            "Element: frame is only supported on the task element",
+            mouse(yaml).unwrap_err().message
+        );
+    }
+
+    #[test]
+    fn test_task_release() {
+        let yaml = "
+                name: 'Drop'
+                mouse:
+                    action: 'release'
+              ";
+
+        let expected = Ok(Mouse {
+            _task_types: TaskTypes::MOUSE,
+            name: "Drop".to_owned(),
+            action: MouseAction::Release,
+            element: None,
+            target: None,
+        });
+        assert_eq!(expected, mouse(yaml))
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+                name: 'Move the card'
+                mouse:
+                    action: 'drag_and_drop'
+                    element:
+                        id: 'card'
+              ";
+        let message = mouse(yaml).unwrap_err().message;
+        assert_eq!("drag_and_drop - expected a target or an offset", message);
+
+        let yaml = "
+                name: 'Open the menu'
+                mouse:
+                    action: 'hover'
+              ";
+        assert_eq!("element field not found", mouse(yaml).unwrap_err().message);
+
+        let yaml = "
+                name: 'Open the menu'
+                mouse:
+                    action: 'swipe'
+                    element:
+                        id: 'menu'
+              ";
+        assert_eq!(
+            "Unknown mouse action: swipe",
+            mouse(yaml).unwrap_err().message
+        );
+    }
+}
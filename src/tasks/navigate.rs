@@ -0,0 +1,346 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::time::{sleep, Duration};
+
+use super::{get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    element::Element,
+    executor::{ExecuteResult, WebDriverSession},
+    matcher::Matcher,
+};
+
+const BACK: &str = "back";
+const FORWARD: &str = "forward";
+const REFRESH: &str = "refresh";
+const DEFAULT_TIMEOUT_MS: u64 = 10000;
+const POLL_MS: u64 = 250;
+
+/// What has to be on the new page before the task is done.
+#[derive(Debug, PartialEq, Eq)]
+enum WaitFor {
+    Element(Element),
+    Url(Matcher),
+    Title(Matcher),
+}
+
+/// Moves through the browser history or reloads the page. The driver waits
+/// for the page load, `wait_for` covers content loaded after it.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Navigate {
+    _task_types: TaskTypes,
+    name: String,
+    wait_for: Option<WaitFor>,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl Task for Navigate {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Navigate> {
+        let name = get_task_name(task)?;
+
+        let (task_type, key, navigate) = if let Some(navigate) = task.get(BACK) {
+            (TaskTypes::BACK, BACK, navigate)
+        } else if let Some(navigate) = task.get(FORWARD) {
+            (TaskTypes::FORWARD, FORWARD, navigate)
+        } else if let Some(navigate) = task.get(REFRESH) {
+            (TaskTypes::REFRESH, REFRESH, navigate)
+        } else {
+            return Err(TaskErr {
+                message: String::from("Malformed Task"),
+                task: Some(task.clone()),
+                task_type: None,
+            });
+        };
+
+        match get_navigate(name, task_type, key, navigate) {
+            Ok(navigate) => Ok(navigate),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(task_type),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.navigate(&web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(self._task_types),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: self._task_types,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Navigate {
+    async fn navigate(&self, web_driver_session: &WebDriverSession) -> Result<(), String> {
+        let driver = &web_driver_session.driver;
+        let navigated = match self._task_types {
+            TaskTypes::BACK => driver.back().await,
+            TaskTypes::FORWARD => driver.forward().await,
+            _ => driver.refresh().await,
+        };
+
+        if let Err(e) = navigated {
+            return Err(format!("Unable to navigate: {}", e));
+        }
+
+        let wait_for = match &self.wait_for {
+            Some(wait_for) => wait_for,
+            None => return Ok(()),
+        };
+
+        let start = Instant::now();
+        loop {
+            if self.is_loaded(wait_for, web_driver_session).await? {
+                return Ok(());
+            }
+            if start.elapsed() >= self.timeout {
+                return Err(format!(
+                    "Page not loaded after {}ms: {}",
+                    self.timeout.as_millis(),
+                    describe(wait_for)
+                ));
+            }
+            sleep(Duration::from_millis(POLL_MS)).await;
+        }
+    }
+
+    async fn is_loaded(
+        &self,
+        wait_for: &WaitFor,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<bool, String> {
+        let driver = &web_driver_session.driver;
+
+        let (actual, matcher) = match wait_for {
+            WaitFor::Element(element) => {
+                // The frame may not be loaded yet either.
+                let frames = match Element::enter_frames(element, web_driver_session).await {
+                    Ok(frames) => frames,
+                    Err(_) => return Ok(false),
+                };
+                let by = Element::find_by_resolve(element, web_driver_session)?;
+                let found = driver.find(by).await.is_ok();
+                Element::leave_frames(frames, web_driver_session).await?;
+                return Ok(found);
+            }
+            WaitFor::Url(matcher) => (
+                driver.current_url().await.map(|url| url.to_string()),
+                matcher,
+            ),
+            WaitFor::Title(matcher) => (driver.title().await, matcher),
+        };
+
+        let actual = match actual {
+            Ok(actual) => actual,
+            Err(e) => return Err(e.to_string()),
+        };
+        let expected = web_driver_session.resolve(&matcher.expected)?;
+        matcher.with_expected(expected).is_match(&actual)
+    }
+}
+
+fn describe(wait_for: &WaitFor) -> String {
+    match wait_for {
+        WaitFor::Element(element) => format!("element [{}] not found", element.value),
+        WaitFor::Url(matcher) => format!("url not {}", matcher.fail_message()),
+        WaitFor::Title(matcher) => format!("title not {}", matcher.fail_message()),
+    }
+}
+
+/// `back: true` only navigates, a map can add `wait_for` and `timeout`.
+/// Anything else, `back: false` included, is rejected rather than navigating.
+fn get_navigate(
+    name: String,
+    task_type: TaskTypes,
+    key: &str,
+    navigate: &Value,
+) -> Result<Navigate, String> {
+    let navigate = match navigate {
+        Value::Mapping(navigate) => navigate,
+        Value::Bool(true) => {
+            return Ok(Navigate {
+                _task_types: task_type,
+                name,
+                wait_for: None,
+                timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            })
+        }
+        _ => return Err(format!("{} - expected true or a map", key)),
+    };
+
+    let wait_for = match navigate.get("wait_for") {
+        None => None,
+        Some(wait_for) => match wait_for.as_mapping() {
+            Some(wait_for) => Some(get_wait_for(wait_for)?),
+            None => return Err("wait_for field is not a map".to_string()),
+        },
+    };
+
+    let timeout = match navigate.get("timeout") {
+        None => DEFAULT_TIMEOUT_MS,
+        Some(timeout) => match timeout.as_u64() {
+            Some(timeout) => timeout,
+            None => return Err("timeout field is not a number".to_string()),
+        },
+    };
+
+    Ok(Navigate {
+        _task_types: task_type,
+        name,
+        wait_for,
+        timeout: Duration::from_millis(timeout),
+    })
+}
+
+fn get_wait_for(wait_for: &Mapping) -> Result<WaitFor, String> {
+    let keys: Vec<&str> = ["element", "url", "title"]
+        .into_iter()
+        .filter(|key| wait_for.contains_key(*key))
+        .collect();
+
+    match keys.as_slice() {
+        ["element"] => Ok(WaitFor::Element(Element::new(wait_for)?)),
+        ["url"] => Ok(WaitFor::Url(Matcher::new(&wait_for["url"])?)),
+        ["title"] => Ok(WaitFor::Title(Matcher::new(&wait_for["title"])?)),
+        [] => Err("wait_for - expected one of element, url or title".to_string()),
+        _ => Err("wait_for - only one of element, url or title is allowed".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Navigate::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'back to the results'
+        back: true
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Navigate::new(&task);
+        let expected = Ok(Navigate {
+            _task_types: TaskTypes::BACK,
+            name: "back to the results".to_owned(),
+            wait_for: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_wait_for() {
+        let yaml = "
+        name: 'reload the orders'
+        refresh:
+            wait_for:
+                element:
+                    id: 'orders'
+            timeout: 5000
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Navigate::new(&task);
+        let expected = Ok(Navigate {
+            _task_types: TaskTypes::REFRESH,
+            name: "reload the orders".to_owned(),
+            wait_for: Some(WaitFor::Element(Element {
+                element_type: ElementType::ID,
+                value: "orders".to_owned(),
+                frame: None,
+            })),
+            timeout: Duration::from_millis(5000),
+        });
+        assert_eq!(expected, result);
+
+        let yaml = "
+        name: 'forward to the article'
+        forward:
+            wait_for:
+                url:
+                    contains: '/article'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Navigate::new(&task).unwrap();
+        let url: Value = serde_yaml::from_str("contains: '/article'").unwrap();
+        let expected = Some(WaitFor::Url(Matcher::new(&url).unwrap()));
+        assert_eq!(TaskTypes::FORWARD, result._task_types);
+        assert_eq!(expected, result.wait_for);
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+        name: 'back to the results'
+        back:
+            wait_for:
+                title: 'Results'
+                url: '/results'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Navigate::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("wait_for - only one of element, url or title is allowed"),
+            task: Some(task),
+            task_type: Some(TaskTypes::BACK),
+        });
+        assert_eq!(expected, result);
+
+        let yaml = "
+        name: 'stay on the results'
+        refresh: false
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Navigate::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("refresh - expected true or a map"),
+            task: Some(task),
+            task_type: Some(TaskTypes::REFRESH),
+        });
+        assert_eq!(expected, result)
+    }
+}
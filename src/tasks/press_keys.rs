@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::time::Instant;
+
+use super::{get_task, get_task_name, resolve_keys, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    executor::{ExecuteResult, WebDriverSession},
+    keys::{parse_keys, to_actions},
+};
+
+const TASK_TYPE: &str = "press_keys";
+
+/// Types into whatever has the focus, like `{ESC}` to close a dialog.
+#[derive(PartialEq, Eq, Debug)]
+pub struct PressKeys {
+    _task_types: TaskTypes,
+    name: String,
+    keys: String,
+}
+
+#[async_trait]
+impl Task for PressKeys {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<PressKeys> {
+        let name = get_task_name(task)?;
+        let press_keys = get_task(task, TASK_TYPE)?;
+
+        match get_keys(press_keys) {
+            Ok(keys) => Ok(PressKeys {
+                _task_types: TaskTypes::PRESSKEYS,
+                name,
+                keys,
+            }),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::PRESSKEYS),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        let keys = match resolve_keys(&self.keys, &web_driver_session) {
+            Ok(keys) => keys,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::PRESSKEYS),
+                    },
+                ))
+            }
+        };
+
+        let actions = to_actions(web_driver_session.driver.action_chain(), &keys);
+        if let Err(e) = actions.perform().await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: format!("Unable to press keys: {}", e),
+                    task: None,
+                    task_type: Some(TaskTypes::PRESSKEYS),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::PRESSKEYS,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+fn get_keys(press_keys: &Mapping) -> Result<String, String> {
+    let keys = match press_keys.get("keys") {
+        Some(keys) => keys,
+        None => return Err("keys field not found".to_string()),
+    };
+
+    match keys.as_str() {
+        Some("") => Err("keys is empty".to_string()),
+        Some(keys) => parse_keys(keys, |_| false).map(|_| keys.to_string()),
+        None => Err("keys is not a string".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = PressKeys::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'close the dialog'
+        press_keys:
+            keys: '{ESC}'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = PressKeys::new(&task);
+        let expected = Ok(PressKeys {
+            _task_types: TaskTypes::PRESSKEYS,
+            name: "close the dialog".to_owned(),
+            keys: "{ESC}".to_owned(),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let yaml = "
+        name: 'close the dialog'
+        press_keys:
+            keys: 2
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = PressKeys::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("keys is not a string"),
+            task: Some(task),
+            task_type: Some(TaskTypes::PRESSKEYS),
+        });
+        assert_eq!(expected, result)
+    }
+}
@@ -1,25 +1,62 @@
-use std::{collections::HashMap, path::Path};
+mod capture;
+mod compare;
+
+use std::{collections::HashMap, fs, path::{Path, PathBuf}, str::FromStr};
 
 use async_trait::async_trait;
 use serde_yaml::{Mapping, Value};
 use std::time::Instant;
-use thirtyfour::{prelude::WebDriverError, By};
+use thirtyfour::{By, WebElement};
 
 use crate::{
     element::Element,
-    executor::{ExecuteResult, WebDriverSession}, variables::resolve_variables,
+    executor::{ExecuteResult, OnFail, WebDriverSession},
 };
 
-use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use self::capture::{add_masks, full_page, remove_masks, ImageFormat};
+use self::compare::{compare, Threshold};
+use super::{
+    failed_validations, get_on_fail, get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult,
+    TaskTypes, ValidationResult, ValidationReultType,
+};
 
 const TASK_TYPE: &str = "screenshot";
 
+/// What to do when the screenshot path already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IfExists {
+    #[default]
+    Overwrite,
+    Suffix,
+    Fail,
+}
+
+impl FromStr for IfExists {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<IfExists, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "overwrite" => Ok(IfExists::Overwrite),
+            "suffix" => Ok(IfExists::Suffix),
+            "fail" => Ok(IfExists::Fail),
+            _ => Err(format!("if_exists: [{}] expected overwrite, suffix or fail", input)),
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct Screenshot {
     _task_types: crate::tasks::TaskTypes,
     name: String,
     path: String,
     element: Option<Element>,
+    mask: Vec<Element>,
+    full_page: bool,
+    format: ImageFormat,
+    compare_to: Option<String>,
+    threshold: Threshold,
+    if_exists: IfExists,
+    on_fail: Option<OnFail>,
 }
 
 #[async_trait]
@@ -38,82 +75,388 @@ impl Task for Screenshot {
             }
         };
 
-        let element = match Element::new(screenshot) {
-            Ok(element) => Some(element),
-            Err(_) => None,
+        let element = match screenshot.contains_key("element") {
+            true => Element::new(screenshot).map(Some),
+            false => Ok(None),
         };
 
-        Ok(Screenshot {
-            _task_types: TaskTypes::SCREENSHOT,
-            name,
-            path,
-            element,
-        })
+        match element.and_then(|element| get_screenshot(name, path, element, screenshot)) {
+            Ok(screenshot) => Ok(screenshot),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::SCREENSHOT),
+            }),
+        }
+    }
+
+    fn element(&self) -> Option<&Element> {
+        self.element.as_ref()
+    }
+
+    fn name(&self) -> &str {
+        &self.name
     }
 
     async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
 
-        if let Some(element) = &self.element {
-            let by: By = Element::find_by_resolve(element, &web_driver_session.variables);
-            let element = match web_driver_session.driver.find(by).await {
-                Ok(element) => element,
-                Err(e) => {
-                    return Err((
-                        web_driver_session,
-                        TaskErr {
-                            message: format!("{}", e),
-                            task: None,
-                            task_type: Some(TaskTypes::SCREENSHOT),
-                        },
-                    ));
-                }
-            };
+        let path = match self.save_screenshot(&web_driver_session).await {
+            Ok(path) => path,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::SCREENSHOT),
+                    },
+                ));
+            }
+        };
 
-            let path = resolve_variables(&self.path, &web_driver_session.variables);
+        let results = match &self.compare_to {
+            Some(baseline) => {
+                vec![self.compare_baseline(&path, baseline, &web_driver_session)]
+            }
+            None => Vec::new(),
+        };
 
-            let screenshot = element.screenshot(Path::new(&path)).await;
-            return screenshot_result(screenshot, web_driver_session, &self.name, start);
+        if self.on_fail.unwrap_or(web_driver_session.on_fail) == OnFail::STOP {
+            if let Some(message) = failed_validations(&results) {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message,
+                        task: None,
+                        task_type: Some(TaskTypes::SCREENSHOT),
+                    },
+                ));
+            }
         }
 
-        let screenshot = web_driver_session
-            .driver
-            .screenshot(Path::new(&self.path))
-            .await;
-
-        screenshot_result(screenshot, web_driver_session, &self.name, start)
-    }
-}
-
-fn screenshot_result(
-    screenshot: Result<(), WebDriverError>,
-    web_driver_session: WebDriverSession,
-    name: &str,
-    start: Instant,
-) -> ExecuteResult {
-    match screenshot {
-        Ok(_) => Ok((
+        let name = self.name.clone();
+        Ok((
             web_driver_session,
             TaskOk {
-                name: name.to_string(),
+                name,
                 task_type: TaskTypes::SCREENSHOT,
                 duration: start.elapsed().as_secs(),
-                result: None,
+                result: self.compare_to.as_ref().map(|_| results),
+                path: Some(path),
             },
-        )),
-        Err(e) => {
-            Err((
-                web_driver_session,
-                TaskErr {
-                    message: format!("Unable to take a screenshot: {:?}", e),
-                    task: None,
-                    task_type: Some(TaskTypes::SCREENSHOT),
+        ))
+    }
+}
+
+impl Screenshot {
+    /// Takes the screenshot and writes it to `path`, returning the resolved path.
+    async fn save_screenshot(&self, web_driver_session: &WebDriverSession) -> Result<String, String> {
+        let path = web_driver_session.resolve(&self.path)?;
+        let path = target_path(&path, self.if_exists)?;
+        let driver = &web_driver_session.driver;
+
+        if !self.mask.is_empty() {
+            let mut elements: Vec<WebElement> = Vec::new();
+            for mask in &self.mask {
+                let by: By = Element::find_by_resolve(mask, web_driver_session)?;
+                match driver.find_all(by).await {
+                    Ok(mut found) => elements.append(&mut found),
+                    Err(e) => return Err(format!("mask: {}", e)),
+                }
+            }
+            add_masks(driver, &elements).await?;
+        }
+
+        let png = self.capture(web_driver_session).await;
+
+        if !self.mask.is_empty() {
+            remove_masks(driver).await;
+        }
+
+        let image = match self.format.encode(png?) {
+            Ok(image) => image,
+            Err(e) => return Err(format!("Unable to take a screenshot: {}", e)),
+        };
+
+        if let Some(parent) = Path::new(&path).parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                return Err(format!("Unable to create directory [{}]: {}", parent.display(), e));
+            }
+        }
+
+        match fs::write(&path, image) {
+            Ok(_) => Ok(path),
+            Err(e) => Err(format!("Unable to take a screenshot: {:?}", e)),
+        }
+    }
+
+    async fn capture(&self, web_driver_session: &WebDriverSession) -> Result<Vec<u8>, String> {
+        let driver = &web_driver_session.driver;
+
+        let png = match &self.element {
+            Some(element) => {
+                let by: By = Element::find_by_resolve(element, web_driver_session)?;
+                match driver.find(by).await {
+                    Ok(element) => element.screenshot_as_png().await,
+                    Err(e) => return Err(format!("{}", e)),
+                }
+            }
+            None if self.full_page => return full_page(driver).await,
+            None => driver.screenshot_as_png().await,
+        };
+
+        match png {
+            Ok(png) => Ok(png),
+            Err(e) => Err(format!("Unable to take a screenshot: {:?}", e)),
+        }
+    }
+
+    fn compare_baseline(
+        &self,
+        path: &str,
+        baseline: &str,
+        web_driver_session: &WebDriverSession,
+    ) -> ValidationResult {
+        let baseline = match web_driver_session.resolve(baseline) {
+            Ok(baseline) => baseline,
+            Err(e) => return failed(e),
+        };
+
+        if web_driver_session.update_baselines {
+            return match update_baseline(path, &baseline) {
+                Ok(_) => ValidationResult {
+                    validation: ValidationReultType::SUCCESS,
+                    message: format!("Pass: Baseline [{}] updated", baseline),
                 },
-            ))
+                Err(e) => failed(format!("Unable to update baseline [{}]: {}", baseline, e)),
+            };
+        }
+
+        if !Path::new(&baseline).exists() {
+            return failed(format!(
+                "Baseline [{}] not found, run with --update-baselines to create it",
+                baseline
+            ));
+        }
+
+        let (actual, expected) = match (image::open(path), image::open(&baseline)) {
+            (Ok(actual), Ok(expected)) => (actual.to_rgba8(), expected.to_rgba8()),
+            (Err(e), _) => return failed(format!("Unable to read screenshot [{}]: {}", path, e)),
+            (_, Err(e)) => return failed(format!("Unable to read baseline [{}]: {}", baseline, e)),
+        };
+
+        let comparison = match compare(&actual, &expected) {
+            Ok(comparison) => comparison,
+            Err(e) => return failed(format!("Screenshot {}", e)),
+        };
+
+        if self.threshold.allows(&comparison) {
+            return ValidationResult {
+                validation: ValidationReultType::SUCCESS,
+                message: format!(
+                    "Pass: Screenshot matches baseline [{}], {} pixels differ ({:.2}%)",
+                    baseline,
+                    comparison.different,
+                    comparison.percent()
+                ),
+            };
+        }
+
+        let diff_path = diff_path(path);
+        let diff = match comparison.diff.save(&diff_path) {
+            Ok(_) => format!(", diff: [{}]", diff_path.display()),
+            Err(e) => format!(", unable to save diff [{}]: {}", diff_path.display(), e),
+        };
+
+        failed(format!(
+            "Screenshot differs from baseline [{}], {} pixels differ ({:.2}%){}",
+            baseline,
+            comparison.different,
+            comparison.percent(),
+            diff
+        ))
+    }
+}
+
+fn failed(message: String) -> ValidationResult {
+    ValidationResult {
+        validation: ValidationReultType::FAILED,
+        message: format!("Failed: {}", message),
+    }
+}
+
+/// Applies `if_exists` to a resolved path, `shot.png` becomes `shot-1.png`
+/// with `suffix`.
+fn target_path(path: &str, if_exists: IfExists) -> Result<String, String> {
+    if !Path::new(path).exists() {
+        return Ok(path.to_string());
+    }
+
+    match if_exists {
+        IfExists::Overwrite => Ok(path.to_string()),
+        IfExists::Fail => Err(format!("Screenshot [{}] already exists", path)),
+        IfExists::Suffix => {
+            let file = Path::new(path);
+            let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+            let mut index = 1;
+            loop {
+                let name = match file.extension() {
+                    Some(extension) => format!("{}-{}.{}", stem, index, extension.to_string_lossy()),
+                    None => format!("{}-{}", stem, index),
+                };
+                let candidate = file.with_file_name(name);
+                if !candidate.exists() {
+                    return Ok(candidate.to_string_lossy().to_string());
+                }
+                index += 1;
+            }
         }
     }
 }
 
+/// The diff image sits next to the screenshot, only its extension changes:
+/// `shot.jpg` gives `shot.diff.png`, `shot.v1.png` gives `shot.v1.diff.png`.
+fn diff_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}.diff.png", stem))
+}
+
+/// Copies the screenshot over the baseline, converting it when the baseline
+/// is in another format, a `.jpg` capture for a `.png` baseline.
+fn update_baseline(path: &str, baseline: &str) -> Result<(), String> {
+    let format = match image::ImageFormat::from_path(baseline) {
+        Ok(format) => format,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    if let Some(parent) = Path::new(baseline).parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    if image::ImageFormat::from_path(path).ok() == Some(format) {
+        return match fs::copy(path, baseline) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        };
+    }
+
+    let image = match image::open(path) {
+        Ok(image) => image,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    // JPEG has no alpha channel.
+    let saved = match format {
+        image::ImageFormat::Jpeg => image.to_rgb8().save_with_format(baseline, format),
+        _ => image.save_with_format(baseline, format),
+    };
+    saved.map_err(|e| e.to_string())
+}
+
+fn get_screenshot(
+    name: String,
+    path: String,
+    element: Option<Element>,
+    screenshot: &Mapping,
+) -> Result<Screenshot, String> {
+    let full_page = match screenshot.get("full_page") {
+        None => false,
+        Some(full_page) => match full_page.as_bool() {
+            Some(full_page) => full_page,
+            None => return Err("full_page field is not a bool".to_string()),
+        },
+    };
+
+    if full_page && element.is_some() {
+        return Err("full_page can`t be used with an element".to_string());
+    }
+
+    Ok(Screenshot {
+        _task_types: TaskTypes::SCREENSHOT,
+        name,
+        format: get_format(screenshot, &path)?,
+        path,
+        element,
+        mask: get_mask(screenshot)?,
+        full_page,
+        compare_to: get_compare_to(screenshot)?,
+        threshold: get_threshold(screenshot)?,
+        if_exists: get_if_exists(screenshot)?,
+        on_fail: get_on_fail(screenshot)?,
+    })
+}
+
+fn get_mask(screenshot: &Mapping) -> Result<Vec<Element>, String> {
+    let mask = match screenshot.get("mask") {
+        None => return Ok(Vec::new()),
+        Some(mask) => match mask.as_sequence() {
+            Some(mask) => mask,
+            None => return Err("mask field is not a list".to_string()),
+        },
+    };
+
+    let mut elements: Vec<Element> = Vec::new();
+    for locator in mask {
+        match locator.as_mapping() {
+            Some(locator) => elements.push(Element::from_locator(locator)?),
+            None => return Err("mask: locator is not a map".to_string()),
+        }
+    }
+
+    Ok(elements)
+}
+
+fn get_format(screenshot: &Mapping, path: &str) -> Result<ImageFormat, String> {
+    let format = match screenshot.get("format") {
+        None => None,
+        Some(format) => match format.as_str() {
+            Some(format) => Some(format),
+            None => return Err("format field is not a string".to_string()),
+        },
+    };
+
+    let quality = match screenshot.get("quality") {
+        None => None,
+        Some(quality) => match quality.as_u64() {
+            Some(quality) => Some(quality),
+            None => return Err("quality field is not a number".to_string()),
+        },
+    };
+
+    ImageFormat::new(format, quality, path)
+}
+
+fn get_compare_to(screenshot: &Mapping) -> Result<Option<String>, String> {
+    match screenshot.get("compare_to") {
+        None => Ok(None),
+        Some(compare_to) => match compare_to.as_str() {
+            Some(compare_to) => Ok(Some(compare_to.to_string())),
+            None => Err("compare_to field is not a string".to_string()),
+        },
+    }
+}
+
+fn get_threshold(screenshot: &Mapping) -> Result<Threshold, String> {
+    match screenshot.get("threshold") {
+        None => Ok(Threshold::default()),
+        Some(Value::Number(pixels)) => Threshold::parse(&pixels.to_string()),
+        Some(Value::String(threshold)) => Threshold::parse(threshold),
+        Some(_) => Err("threshold field is not a number or a percentage".to_string()),
+    }
+}
+
+fn get_if_exists(screenshot: &Mapping) -> Result<IfExists, String> {
+    match screenshot.get("if_exists") {
+        None => Ok(IfExists::default()),
+        Some(if_exists) => match if_exists.as_str() {
+            Some(if_exists) => IfExists::from_str(if_exists),
+            None => Err("if_exists field is not a string".to_string()),
+        },
+    }
+}
+
 fn get_path(screenshot: &Mapping) -> Result<String, String> {
     let screenshot_path = match screenshot.get("path") {
         Some(screenshot_path) => screenshot_path,
@@ -194,6 +537,13 @@ mod tests {
             name: "Take a screenshot".to_owned(),
             path: "/tmp/screenshot.png".to_owned(),
             element: None,
+            mask: Vec::new(),
+            full_page: false,
+            format: ImageFormat::Png,
+            compare_to: None,
+            threshold: Threshold::default(),
+            if_exists: IfExists::default(),
+            on_fail: None,
         });
         assert_eq!(expected, result)
     }
@@ -217,7 +567,15 @@ mod tests {
             element: Some(Element {
                 element_type: ElementType::XPATH,
                 value: "search-form".to_owned(),
+                frame: None,
             }),
+            mask: Vec::new(),
+            full_page: false,
+            format: ImageFormat::Png,
+            compare_to: None,
+            threshold: Threshold::default(),
+            if_exists: IfExists::default(),
+            on_fail: None,
         });
         assert_eq!(expected, result)
     }
@@ -269,6 +627,27 @@ mod tests {
                         foo: 'search-form'
               ";
 
+        let screenshot = serde_yaml::from_str(yaml).unwrap();
+        let result = Screenshot::new(&screenshot);
+        let expected = Err(TaskErr {
+            message: String::from("Unknow Element Type: \"foo\""),
+            task: Some(screenshot),
+            task_type: Some(TaskTypes::SCREENSHOT),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_compare_to() {
+        let yaml = "
+                name: 'Take a screenshot'
+                screenshot:
+                    path: '/tmp/screenshot.png'
+                    compare_to: 'baselines/home.png'
+                    threshold: '0.5%'
+                    on_fail: stop
+              ";
+
         let screenshot = serde_yaml::from_str(yaml).unwrap();
         let result = Screenshot::new(&screenshot);
         let expected = Ok(Screenshot {
@@ -276,7 +655,164 @@ mod tests {
             name: "Take a screenshot".to_owned(),
             path: "/tmp/screenshot.png".to_owned(),
             element: None,
+            mask: Vec::new(),
+            full_page: false,
+            format: ImageFormat::Png,
+            compare_to: Some("baselines/home.png".to_owned()),
+            threshold: Threshold::Percent(0.5),
+            if_exists: IfExists::default(),
+            on_fail: Some(OnFail::STOP),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_invalid_threshold() {
+        let yaml = "
+                name: 'Take a screenshot'
+                screenshot:
+                    path: '/tmp/screenshot.png'
+                    compare_to: 'baselines/home.png'
+                    threshold: 'a few'
+              ";
+
+        let screenshot = serde_yaml::from_str(yaml).unwrap();
+        let result = Screenshot::new(&screenshot);
+        let expected = Err(TaskErr {
+            message: String::from("threshold: [a few] expected pixels 100 or percentage '0.5%'"),
+            task: Some(screenshot),
+            task_type: Some(TaskTypes::SCREENSHOT),
         });
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn test_task_mask_full_page_jpeg() {
+        let yaml = "
+                name: 'Take a screenshot'
+                screenshot:
+                    path: '/tmp/screenshot.jpg'
+                    full_page: true
+                    quality: 70
+                    mask:
+                        - className: 'timestamp'
+                        - id: 'ads'
+              ";
+
+        let screenshot = serde_yaml::from_str(yaml).unwrap();
+        let result = Screenshot::new(&screenshot);
+        let expected = Ok(Screenshot {
+            _task_types: TaskTypes::SCREENSHOT,
+            name: "Take a screenshot".to_owned(),
+            path: "/tmp/screenshot.jpg".to_owned(),
+            element: None,
+            mask: vec![
+                Element {
+                    element_type: ElementType::CLASSNAME,
+                    value: "timestamp".to_owned(),
+                    frame: None,
+                },
+                Element {
+                    element_type: ElementType::ID,
+                    value: "ads".to_owned(),
+                    frame: None,
+                },
+            ],
+            full_page: true,
+            format: ImageFormat::Jpeg(70),
+            compare_to: None,
+            threshold: Threshold::default(),
+            if_exists: IfExists::default(),
+            on_fail: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_full_page_element() {
+        let yaml = "
+                name: 'Take a screenshot'
+                screenshot:
+                    path: '/tmp/screenshot.png'
+                    full_page: true
+                    element:
+                        id: 'content'
+              ";
+
+        let screenshot = serde_yaml::from_str(yaml).unwrap();
+        let result = Screenshot::new(&screenshot);
+        let expected = Err(TaskErr {
+            message: String::from("full_page can`t be used with an element"),
+            task: Some(screenshot),
+            task_type: Some(TaskTypes::SCREENSHOT),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_if_exists() {
+        let yaml = "
+                name: 'Take a screenshot'
+                screenshot:
+                    path: '/tmp/screenshot.png'
+                    if_exists: 'remove'
+              ";
+
+        let screenshot = serde_yaml::from_str(yaml).unwrap();
+        let result = Screenshot::new(&screenshot);
+        let expected = Err(TaskErr {
+            message: String::from("if_exists: [remove] expected overwrite, suffix or fail"),
+            task: Some(screenshot),
+            task_type: Some(TaskTypes::SCREENSHOT),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_target_path() {
+        let dir = std::env::temp_dir().join(format!("ls-target-path-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("shot.png");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(Ok(path.to_string()), target_path(path, IfExists::Fail));
+
+        fs::write(path, "").unwrap();
+        fs::write(dir.join("shot-1.png"), "").unwrap();
+        let suffixed = dir.join("shot-2.png").to_str().unwrap().to_string();
+        assert_eq!(Ok(suffixed), target_path(path, IfExists::Suffix));
+        assert_eq!(Ok(path.to_string()), target_path(path, IfExists::Overwrite));
+
+        let expected = Err(format!("Screenshot [{}] already exists", path));
+        assert_eq!(expected, target_path(path, IfExists::Fail));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff_path() {
+        assert_eq!(PathBuf::from("shots/home.diff.png"), diff_path("shots/home.png"));
+        assert_eq!(PathBuf::from("shots/home.v1.diff.png"), diff_path("shots/home.v1.png"));
+        assert_eq!(PathBuf::from("home.diff.png"), diff_path("home.jpg"));
+        assert_eq!(PathBuf::from("home.diff.png"), diff_path("home"));
+    }
+
+    #[test]
+    fn test_update_baseline_format() {
+        let dir = std::env::temp_dir().join(format!("ls-update-baseline-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let shot = dir.join("shot.jpg");
+        let shot = shot.to_str().unwrap();
+        image::RgbImage::new(2, 2).save(shot).unwrap();
+
+        let baseline = dir.join("baselines").join("shot.png");
+        let baseline = baseline.to_str().unwrap();
+        assert_eq!(Ok(()), update_baseline(shot, baseline));
+        assert!(fs::read(baseline).unwrap().starts_with(b"\x89PNG"));
+        assert!(image::open(baseline).is_ok());
+
+        assert!(update_baseline(shot, dir.join("shot.txt").to_str().unwrap()).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
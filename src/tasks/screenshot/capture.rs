@@ -0,0 +1,217 @@
+use std::io::Cursor;
+
+use image::{codecs::jpeg::JpegEncoder, imageops, DynamicImage, ImageOutputFormat, RgbaImage};
+use serde::Deserialize;
+use serde_json::Value;
+use thirtyfour::{WebDriver, WebElement};
+
+const DEFAULT_QUALITY: u8 = 90;
+
+// The overlays are absolute so they stay on the element while scrolling.
+const ADD_MASKS: &str = r#"
+for (const element of arguments) {
+    const rect = element.getBoundingClientRect();
+    const mask = document.createElement('div');
+    mask.setAttribute('data-ls-mask', '');
+    mask.style.cssText = 'position:absolute;background:#000;pointer-events:none;z-index:2147483647;'
+        + 'left:' + (rect.left + window.scrollX) + 'px;top:' + (rect.top + window.scrollY) + 'px;'
+        + 'width:' + rect.width + 'px;height:' + rect.height + 'px';
+    document.body.appendChild(mask);
+}
+"#;
+const REMOVE_MASKS: &str = "document.querySelectorAll('[data-ls-mask]').forEach(m => m.remove());";
+
+const PAGE_SIZE: &str = r#"
+const root = document.documentElement;
+return {
+    width: Math.max(root.scrollWidth, document.body.scrollWidth),
+    height: Math.max(root.scrollHeight, document.body.scrollHeight),
+    viewport_width: root.clientWidth,
+    viewport_height: root.clientHeight,
+    scroll_x: window.scrollX,
+    scroll_y: window.scrollY
+};
+"#;
+const SCROLL_TO: &str =
+    "window.scrollTo(arguments[0], arguments[1]); return [window.scrollX, window.scrollY];";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg(u8),
+}
+
+impl ImageFormat {
+    /// `format` wins over the extension of `path`, png by default.
+    pub fn new(format: Option<&str>, quality: Option<u64>, path: &str) -> Result<Self, String> {
+        let (format, explicit) = match format {
+            Some(format) => (format.to_lowercase(), true),
+            None => (path.rsplit('.').next().unwrap_or_default().to_lowercase(), false),
+        };
+
+        let quality = match quality {
+            None => DEFAULT_QUALITY,
+            Some(quality) if (1..=100).contains(&quality) => quality as u8,
+            Some(quality) => return Err(format!("quality: [{}] must be between 1 and 100", quality)),
+        };
+
+        match format.as_str() {
+            "jpg" | "jpeg" => Ok(ImageFormat::Jpeg(quality)),
+            "png" => Ok(ImageFormat::Png),
+            _ if explicit => Err(format!("format: [{}] expected png or jpeg", format)),
+            _ => Ok(ImageFormat::Png),
+        }
+    }
+
+    /// Re-encodes a png screenshot, png is written as the browser returned it.
+    pub fn encode(&self, png: Vec<u8>) -> Result<Vec<u8>, String> {
+        let quality = match self {
+            ImageFormat::Png => return Ok(png),
+            ImageFormat::Jpeg(quality) => *quality,
+        };
+
+        let image = match image::load_from_memory(&png) {
+            Ok(image) => image.to_rgb8(),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let mut jpeg: Vec<u8> = Vec::new();
+        match JpegEncoder::new_with_quality(&mut jpeg, quality).encode_image(&image) {
+            Ok(_) => Ok(jpeg),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+pub async fn add_masks(driver: &WebDriver, elements: &[WebElement]) -> Result<(), String> {
+    let mut args: Vec<Value> = Vec::new();
+    for element in elements {
+        match element.to_json() {
+            Ok(element) => args.push(element),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    match driver.execute(ADD_MASKS, args).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Unable to mask elements: {}", e)),
+    }
+}
+
+pub async fn remove_masks(driver: &WebDriver) {
+    // Best effort, a failed capture should report its own error.
+    let _ = driver.execute(REMOVE_MASKS, Vec::new()).await;
+}
+
+#[derive(Deserialize)]
+struct PageSize {
+    width: u32,
+    height: u32,
+    viewport_width: u32,
+    viewport_height: u32,
+    scroll_x: f64,
+    scroll_y: f64,
+}
+
+/// Scrolls through the page one viewport at a time and stitches the
+/// screenshots together, as png.
+pub async fn full_page(driver: &WebDriver) -> Result<Vec<u8>, String> {
+    let size: PageSize = match driver.execute(PAGE_SIZE, Vec::new()).await {
+        Ok(ret) => match ret.convert() {
+            Ok(size) => size,
+            Err(e) => return Err(e.to_string()),
+        },
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut page: Option<RgbaImage> = None;
+    let mut scale = 1.0;
+
+    let mut y = 0;
+    while y < size.height {
+        let mut x = 0;
+        while x < size.width {
+            let (scroll_x, scroll_y) = scroll_to(driver, x, y).await?;
+            let viewport = screenshot(driver).await?;
+
+            // Screenshots are in device pixels, the page size in css pixels.
+            let page = page.get_or_insert_with(|| {
+                scale = viewport.width() as f64 / size.viewport_width.max(1) as f64;
+                RgbaImage::new(
+                    (size.width as f64 * scale) as u32,
+                    (size.height as f64 * scale) as u32,
+                )
+            });
+            imageops::replace(
+                page,
+                &viewport,
+                (scroll_x * scale) as i64,
+                (scroll_y * scale) as i64,
+            );
+
+            x += size.viewport_width.max(1);
+        }
+        y += size.viewport_height.max(1);
+    }
+
+    scroll_to(driver, size.scroll_x as u32, size.scroll_y as u32).await?;
+
+    let page = match page {
+        Some(page) => DynamicImage::ImageRgba8(page),
+        None => return Err("Page is empty".to_string()),
+    };
+
+    let mut png = Cursor::new(Vec::new());
+    match page.write_to(&mut png, ImageOutputFormat::Png) {
+        Ok(_) => Ok(png.into_inner()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+async fn scroll_to(driver: &WebDriver, x: u32, y: u32) -> Result<(f64, f64), String> {
+    let args = vec![Value::from(x), Value::from(y)];
+    match driver.execute(SCROLL_TO, args).await {
+        Ok(ret) => match ret.convert::<(f64, f64)>() {
+            Ok(scroll) => Ok(scroll),
+            Err(e) => Err(e.to_string()),
+        },
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+async fn screenshot(driver: &WebDriver) -> Result<RgbaImage, String> {
+    let png = match driver.screenshot_as_png().await {
+        Ok(png) => png,
+        Err(e) => return Err(format!("Unable to take a screenshot: {:?}", e)),
+    };
+
+    match image::load_from_memory(&png) {
+        Ok(image) => Ok(image.to_rgba8()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Ok(ImageFormat::Png), ImageFormat::new(None, None, "/tmp/a.png"));
+        assert_eq!(Ok(ImageFormat::Jpeg(90)), ImageFormat::new(None, None, "/tmp/a.JPG"));
+        assert_eq!(Ok(ImageFormat::Jpeg(60)), ImageFormat::new(Some("jpeg"), Some(60), "/tmp/a"));
+        assert!(ImageFormat::new(Some("jpeg"), Some(0), "/tmp/a").is_err());
+        assert!(ImageFormat::new(Some("gif"), None, "/tmp/a.png").is_err());
+    }
+
+    #[test]
+    fn test_encode_jpeg() {
+        let mut png = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(RgbaImage::new(2, 2))
+            .write_to(&mut png, ImageOutputFormat::Png)
+            .unwrap();
+
+        let jpeg = ImageFormat::Jpeg(80).encode(png.into_inner()).unwrap();
+        assert_eq!(Ok(image::ImageFormat::Jpeg), image::guess_format(&jpeg).map_err(|_| ()));
+    }
+}
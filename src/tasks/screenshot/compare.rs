@@ -0,0 +1,128 @@
+use image::{Rgba, RgbaImage};
+
+const DIFF_COLOR: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+/// How many pixels may differ from the baseline, `threshold: 100` or
+/// `threshold: '0.5%'`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Threshold {
+    Pixels(u64),
+    Percent(f64),
+}
+
+// Percentages are parsed from yaml, never NaN.
+impl Eq for Threshold {}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Threshold::Pixels(0)
+    }
+}
+
+impl Threshold {
+    pub fn parse(text: &str) -> Result<Threshold, String> {
+        let text = text.trim();
+        let threshold = match text.strip_suffix('%') {
+            Some(percent) => percent.trim().parse::<f64>().ok().map(Threshold::Percent),
+            None => text.parse::<u64>().ok().map(Threshold::Pixels),
+        };
+
+        match threshold {
+            Some(Threshold::Percent(p)) if !(0.0..=100.0).contains(&p) => {
+                Err(format!("threshold: [{}] must be between 0% and 100%", text))
+            }
+            Some(threshold) => Ok(threshold),
+            None => Err(format!("threshold: [{}] expected pixels 100 or percentage '0.5%'", text)),
+        }
+    }
+
+    pub fn allows(&self, comparison: &Comparison) -> bool {
+        match self {
+            Threshold::Pixels(pixels) => comparison.different <= *pixels,
+            Threshold::Percent(percent) => comparison.percent() <= *percent,
+        }
+    }
+}
+
+pub struct Comparison {
+    pub different: u64,
+    pub total: u64,
+    /// The baseline faded out, with the different pixels in red.
+    pub diff: RgbaImage,
+}
+
+impl Comparison {
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.different as f64 * 100.0 / self.total as f64
+    }
+}
+
+pub fn compare(actual: &RgbaImage, baseline: &RgbaImage) -> Result<Comparison, String> {
+    if actual.dimensions() != baseline.dimensions() {
+        return Err(format!(
+            "size [{}x{}] differs from baseline [{}x{}]",
+            actual.width(),
+            actual.height(),
+            baseline.width(),
+            baseline.height()
+        ));
+    }
+
+    let mut different = 0;
+    let mut diff = RgbaImage::new(baseline.width(), baseline.height());
+
+    for (x, y, pixel) in baseline.enumerate_pixels() {
+        if actual.get_pixel(x, y) != pixel {
+            different += 1;
+            diff.put_pixel(x, y, DIFF_COLOR);
+            continue;
+        }
+        let Rgba([r, g, b, _]) = *pixel;
+        diff.put_pixel(x, y, Rgba([r, g, b, 64]));
+    }
+
+    Ok(Comparison {
+        different,
+        total: baseline.width() as u64 * baseline.height() as u64,
+        diff,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_threshold() {
+        assert_eq!(Ok(Threshold::Pixels(100)), Threshold::parse("100"));
+        assert_eq!(Ok(Threshold::Percent(0.5)), Threshold::parse("0.5 %"));
+        assert!(Threshold::parse("120%").is_err());
+        assert!(Threshold::parse("-1").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = RgbaImage::from_pixel(4, 5, Rgba([0, 0, 0, 255]));
+        let mut actual = baseline.clone();
+        actual.put_pixel(1, 1, Rgba([255, 255, 255, 255]));
+
+        let comparison = compare(&actual, &baseline).unwrap();
+        assert_eq!(1, comparison.different);
+        assert_eq!(5.0, comparison.percent());
+        assert_eq!(&DIFF_COLOR, comparison.diff.get_pixel(1, 1));
+
+        assert!(Threshold::Pixels(1).allows(&comparison));
+        assert!(!Threshold::Percent(4.0).allows(&comparison));
+    }
+
+    #[test]
+    fn test_compare_size() {
+        let baseline = RgbaImage::new(4, 5);
+        let actual = RgbaImage::new(5, 5);
+        let expected = "size [5x5] differs from baseline [4x5]".to_string();
+        assert_eq!(Some(expected), compare(&actual, &baseline).err());
+    }
+}
@@ -0,0 +1,413 @@
+use async_trait::async_trait;
+use serde_json::json;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Instant;
+use thirtyfour::{By, WebElement};
+use tokio::time::{sleep, Duration};
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    element::Element,
+    executor::{ExecuteResult, WebDriverSession},
+};
+
+const TASK_TYPE: &str = "scroll";
+const DEFAULT_MAX: u64 = 10;
+const DEFAULT_DELAY_MS: u64 = 500;
+
+const INTO_VIEW: &str = "arguments[0].scrollIntoView({block: arguments[1], inline: 'nearest'});";
+const SCROLL_BY: &str = "window.scrollBy(arguments[0], arguments[1]);";
+const SCROLL_TOP: &str = "window.scrollTo(window.scrollX, 0);";
+const SCROLL_BOTTOM: &str = r#"
+const root = document.documentElement;
+window.scrollTo(window.scrollX, Math.max(root.scrollHeight, document.body.scrollHeight));
+"#;
+
+/// Where the element ends up in the window, `scrollIntoView` block values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Align {
+    Start,
+    #[default]
+    Center,
+    End,
+    Nearest,
+}
+
+impl FromStr for Align {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Align, Self::Err> {
+        match input {
+            "start" => Ok(Align::Start),
+            "center" => Ok(Align::Center),
+            "end" => Ok(Align::End),
+            "nearest" => Ok(Align::Nearest),
+            _ => Err(format!(
+                "align: [{}] expected start, center, end or nearest",
+                input
+            )),
+        }
+    }
+}
+
+impl Align {
+    fn as_str(&self) -> &str {
+        match self {
+            Align::Start => "start",
+            Align::Center => "center",
+            Align::End => "end",
+            Align::Nearest => "nearest",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ScrollTo {
+    Element(Element, Align),
+    By(i64, i64),
+    Top,
+    Bottom,
+    /// Scrolls to the bottom until the element shows up, for infinite feeds.
+    Until {
+        element: Element,
+        align: Align,
+        max: u64,
+        delay: Duration,
+    },
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Scroll {
+    _task_types: TaskTypes,
+    name: String,
+    scroll_to: ScrollTo,
+}
+
+#[async_trait]
+impl Task for Scroll {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Scroll> {
+        let name = get_task_name(task)?;
+        let scroll = get_task(task, TASK_TYPE)?;
+
+        match get_scroll_to(scroll) {
+            Ok(scroll_to) => Ok(Scroll {
+                _task_types: TaskTypes::SCROLL,
+                name,
+                scroll_to,
+            }),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::SCROLL),
+            }),
+        }
+    }
+
+    fn element(&self) -> Option<&Element> {
+        match &self.scroll_to {
+            ScrollTo::Element(element, _) | ScrollTo::Until { element, .. } => Some(element),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.scroll(&web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(TaskTypes::SCROLL),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::SCROLL,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Scroll {
+    async fn scroll(&self, web_driver_session: &WebDriverSession) -> Result<(), String> {
+        let driver = &web_driver_session.driver;
+
+        match &self.scroll_to {
+            ScrollTo::Element(element, align) => {
+                let by: By = Element::find_by_resolve(element, web_driver_session)?;
+                match driver.find(by).await {
+                    Ok(element) => into_view(web_driver_session, &element, *align).await,
+                    Err(e) => Err(format!("{}", e)),
+                }
+            }
+            ScrollTo::By(x, y) => {
+                run(web_driver_session, SCROLL_BY, vec![json!(x), json!(y)]).await
+            }
+            ScrollTo::Top => run(web_driver_session, SCROLL_TOP, Vec::new()).await,
+            ScrollTo::Bottom => run(web_driver_session, SCROLL_BOTTOM, Vec::new()).await,
+            ScrollTo::Until {
+                element,
+                align,
+                max,
+                delay,
+            } => {
+                let by: By = Element::find_by_resolve(element, web_driver_session)?;
+                for count in 0..=*max {
+                    // Only an empty result means "not loaded yet".
+                    let found = match driver.find_all(by.clone()).await {
+                        Ok(found) => found,
+                        Err(e) => return Err(e.to_string()),
+                    };
+                    if let Some(found) = found.first() {
+                        return into_view(web_driver_session, found, *align).await;
+                    }
+                    if count < *max {
+                        run(web_driver_session, SCROLL_BOTTOM, Vec::new()).await?;
+                        sleep(*delay).await;
+                    }
+                }
+                Err(format!(
+                    "Element not found after scrolling {} times - Type: {:?}, Value: {}",
+                    max, element.element_type, element.value
+                ))
+            }
+        }
+    }
+}
+
+async fn into_view(
+    web_driver_session: &WebDriverSession,
+    element: &WebElement,
+    align: Align,
+) -> Result<(), String> {
+    let element = match element.to_json() {
+        Ok(element) => element,
+        Err(e) => return Err(e.to_string()),
+    };
+    run(
+        web_driver_session,
+        INTO_VIEW,
+        vec![element, json!(align.as_str())],
+    )
+    .await
+}
+
+async fn run(
+    web_driver_session: &WebDriverSession,
+    script: &str,
+    args: Vec<serde_json::Value>,
+) -> Result<(), String> {
+    match web_driver_session.driver.execute(script, args).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Unable to scroll: {}", e)),
+    }
+}
+
+fn get_scroll_to(scroll: &Mapping) -> Result<ScrollTo, String> {
+    let keys: Vec<&str> = ["element", "by", "to", "until"]
+        .into_iter()
+        .filter(|key| scroll.contains_key(*key))
+        .collect();
+
+    let align = match scroll.get("align") {
+        None => Align::default(),
+        Some(align) => match align.as_str() {
+            Some(align) => Align::from_str(align)?,
+            None => return Err("align field is not a string".to_string()),
+        },
+    };
+
+    match keys.as_slice() {
+        ["element"] => Ok(ScrollTo::Element(Element::new(scroll)?, align)),
+        ["by"] => get_by(scroll),
+        ["to"] => match scroll.get("to").and_then(|to| to.as_str()) {
+            Some("top") => Ok(ScrollTo::Top),
+            Some("bottom") => Ok(ScrollTo::Bottom),
+            _ => Err("to - expected top or bottom".to_string()),
+        },
+        ["until"] => {
+            let element = match scroll.get("until").and_then(|until| until.as_mapping()) {
+                Some(until) => Element::with_frame(until)?,
+                None => return Err("until field is not a map".to_string()),
+            };
+            Ok(ScrollTo::Until {
+                element,
+                align,
+                max: get_u64(scroll, "max", DEFAULT_MAX)?,
+                delay: Duration::from_millis(get_u64(scroll, "delay", DEFAULT_DELAY_MS)?),
+            })
+        }
+        [] => Err("Expected one of element, by, to or until".to_string()),
+        _ => Err("Only one of element, by, to or until is allowed".to_string()),
+    }
+}
+
+fn get_by(scroll: &Mapping) -> Result<ScrollTo, String> {
+    let by = match scroll.get("by").and_then(|by| by.as_mapping()) {
+        Some(by) => by,
+        None => return Err("by field is not a map".to_string()),
+    };
+
+    let axis = |key: &str| match by.get(key) {
+        None => Ok(0),
+        Some(value) => match value.as_i64() {
+            Some(value) => Ok(value),
+            None => Err(format!("by - {} is not a number", key)),
+        },
+    };
+
+    Ok(ScrollTo::By(axis("x")?, axis("y")?))
+}
+
+fn get_u64(scroll: &Mapping, key: &str, default: u64) -> Result<u64, String> {
+    match scroll.get(key) {
+        None => Ok(default),
+        Some(value) => match value.as_u64() {
+            Some(value) => Ok(value),
+            None => Err(format!("{} field is not a number", key)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    fn scroll_to(yaml: &str) -> Result<ScrollTo, String> {
+        let task = serde_yaml::from_str(yaml).unwrap();
+        Scroll::new(&task)
+            .map(|scroll| scroll.scroll_to)
+            .map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Scroll::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_element() {
+        let yaml = "
+        name: 'show the footer'
+        scroll:
+            element:
+                id: 'footer'
+            align: 'start'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Scroll::new(&task);
+        let expected = Ok(Scroll {
+            _task_types: TaskTypes::SCROLL,
+            name: "show the footer".to_owned(),
+            scroll_to: ScrollTo::Element(
+                Element {
+                    element_type: ElementType::ID,
+                    value: "footer".to_owned(),
+                    frame: None,
+                },
+                Align::Start,
+            ),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_by_and_to() {
+        let yaml = "
+        name: 'scroll down'
+        scroll:
+            by:
+                y: 500
+              ";
+        assert_eq!(Ok(ScrollTo::By(0, 500)), scroll_to(yaml));
+
+        let yaml = "
+        name: 'back to top'
+        scroll:
+            to: 'top'
+              ";
+        assert_eq!(Ok(ScrollTo::Top), scroll_to(yaml));
+    }
+
+    #[test]
+    fn test_task_until() {
+        let yaml = "
+        name: 'load the feed'
+        scroll:
+            until:
+                id: 'item-100'
+            max: 20
+              ";
+
+        let expected = Ok(ScrollTo::Until {
+            element: Element {
+                element_type: ElementType::ID,
+                value: "item-100".to_owned(),
+                frame: None,
+            },
+            align: Align::Center,
+            max: 20,
+            delay: Duration::from_millis(DEFAULT_DELAY_MS),
+        });
+        assert_eq!(expected, scroll_to(yaml));
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+        name: 'scroll'
+        scroll:
+            to: 'left'
+              ";
+        assert_eq!(
+            Err("to - expected top or bottom".to_string()),
+            scroll_to(yaml)
+        );
+
+        let yaml = "
+        name: 'scroll'
+        scroll:
+            to: 'top'
+            by:
+                y: 10
+              ";
+        let expected = Err("Only one of element, by, to or until is allowed".to_string());
+        assert_eq!(expected, scroll_to(yaml));
+
+        let yaml = "
+        name: 'scroll'
+        scroll:
+            element:
+                id: 'footer'
+            align: 'middle'
+              ";
+        let expected = Err("align: [middle] expected start, center, end or nearest".to_string());
+        assert_eq!(expected, scroll_to(yaml));
+    }
+}
@@ -0,0 +1,331 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::time::Instant;
+use thirtyfour::{components::SelectElement, error::WebDriverError, By};
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    element::{xpath_literal, Element},
+    executor::{ExecuteResult, WebDriverSession},
+};
+
+const TASK_TYPE: &str = "select";
+
+/// How the options are picked, every value selects one option so a list
+/// selects several options of a multi-select.
+#[derive(PartialEq, Eq, Debug)]
+enum SelectBy {
+    Value(Vec<String>),
+    Text(Vec<String>),
+    Index(Vec<String>),
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Select {
+    _task_types: TaskTypes,
+    name: String,
+    element: Element,
+    select_by: Option<SelectBy>,
+    deselect_all: bool,
+}
+
+#[async_trait]
+impl Task for Select {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Select> {
+        let name = get_task_name(task)?;
+        let select = get_task(task, TASK_TYPE)?;
+
+        match get_select(name, select) {
+            Ok(select) => Ok(select),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::SELECT),
+            }),
+        }
+    }
+
+    fn element(&self) -> Option<&Element> {
+        Some(&self.element)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.select(&web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(TaskTypes::SELECT),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::SELECT,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Select {
+    async fn select(&self, web_driver_session: &WebDriverSession) -> Result<(), String> {
+        let by: By = Element::find_by_resolve(&self.element, web_driver_session)?;
+        let element = match web_driver_session.driver.find(by).await {
+            Ok(element) => element,
+            Err(e) => return Err(format!("{}", e)),
+        };
+
+        let select = match SelectElement::new(&element).await {
+            Ok(select) => select,
+            Err(e) => return Err(format!("Element is not a select: {}", e)),
+        };
+
+        if self.deselect_all {
+            if let Err(e) = select.deselect_all().await {
+                return Err(format!("Unable to deselect options: {}", e));
+            }
+        }
+
+        let (values, label) = match &self.select_by {
+            Some(SelectBy::Value(values)) => (values, "value"),
+            Some(SelectBy::Text(values)) => (values, "text"),
+            Some(SelectBy::Index(values)) => (values, "index"),
+            None => return Ok(()),
+        };
+
+        for value in values {
+            let value = web_driver_session.resolve(value)?;
+            let selected = match &self.select_by {
+                // Selecting a missing value is not an error for the driver.
+                Some(SelectBy::Value(_)) => {
+                    let xpath = format!(".//option[@value={}]", xpath_literal(&value));
+                    match element.find_all(By::XPath(&xpath)).await {
+                        Ok(options) if options.is_empty() => {
+                            return Err(format!("No option with {} [{}]", label, value))
+                        }
+                        Ok(_) => select.select_by_value(&value).await,
+                        Err(e) => Err(e),
+                    }
+                }
+                Some(SelectBy::Text(_)) => select.select_by_exact_text(&value).await,
+                _ => match value.trim().parse::<u32>() {
+                    Ok(index) => select.select_by_index(index).await,
+                    Err(_) => return Err(format!("by_index: [{}] is not a number", value)),
+                },
+            };
+
+            match selected {
+                Ok(_) => {}
+                Err(WebDriverError::NoSuchElement(_)) => {
+                    return Err(format!("No option with {} [{}]", label, value))
+                }
+                Err(e) => return Err(format!("Unable to select {} [{}]: {}", label, value, e)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn get_select(name: String, select: &Mapping) -> Result<Select, String> {
+    let element = Element::new(select)?;
+
+    let deselect_all = match select.get("deselect_all") {
+        None => false,
+        Some(deselect_all) => match deselect_all.as_bool() {
+            Some(deselect_all) => deselect_all,
+            None => return Err("deselect_all field is not a bool".to_string()),
+        },
+    };
+
+    let mut select_by: Option<SelectBy> = None;
+    for key in ["by_value", "by_text", "by_index"] {
+        let values = match select.get(key) {
+            Some(values) => get_values(key, values)?,
+            None => continue,
+        };
+
+        if select_by.is_some() {
+            return Err("Only one of by_value, by_text or by_index is allowed".to_string());
+        }
+
+        select_by = Some(match key {
+            "by_value" => SelectBy::Value(values),
+            "by_text" => SelectBy::Text(values),
+            _ => SelectBy::Index(values),
+        });
+    }
+
+    if select_by.is_none() && !deselect_all {
+        return Err("Expected one of by_value, by_text, by_index or deselect_all".to_string());
+    }
+
+    Ok(Select {
+        _task_types: TaskTypes::SELECT,
+        name,
+        element,
+        select_by,
+        deselect_all,
+    })
+}
+
+/// A single value or a list of values, numbers are kept as text so indexes
+/// can come from variables.
+fn get_values(key: &str, value: &Value) -> Result<Vec<String>, String> {
+    let values = match value {
+        Value::Sequence(values) => values.iter().collect(),
+        value => vec![value],
+    };
+
+    if values.is_empty() {
+        return Err(format!("{} - list is empty", key));
+    }
+
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::String(value) => Ok(value.clone()),
+            Value::Number(value) => Ok(value.to_string()),
+            _ => Err(format!("{} - value is not a string or a number", key)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    #[test]
+    fn test_empty_task() {
+        let select = HashMap::new();
+        let result = Select::new(&select);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(select),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_by_text() {
+        let yaml = "
+                name: 'Pick a country'
+                select:
+                    element:
+                        id: 'country'
+                    by_text: '{country}'
+              ";
+
+        let select = serde_yaml::from_str(yaml).unwrap();
+        let result = Select::new(&select);
+        let expected = Ok(Select {
+            _task_types: TaskTypes::SELECT,
+            name: "Pick a country".to_owned(),
+            element: Element {
+                element_type: ElementType::ID,
+                value: "country".to_owned(),
+                frame: None,
+            },
+            select_by: Some(SelectBy::Text(vec!["{country}".to_owned()])),
+            deselect_all: false,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_multi_select() {
+        let yaml = "
+                name: 'Pick languages'
+                select:
+                    element:
+                        id: 'languages'
+                    by_index: [0, 2]
+                    deselect_all: true
+              ";
+
+        let select = serde_yaml::from_str(yaml).unwrap();
+        let result = Select::new(&select);
+        let expected = Ok(Select {
+            _task_types: TaskTypes::SELECT,
+            name: "Pick languages".to_owned(),
+            element: Element {
+                element_type: ElementType::ID,
+                value: "languages".to_owned(),
+                frame: None,
+            },
+            select_by: Some(SelectBy::Index(vec!["0".to_owned(), "2".to_owned()])),
+            deselect_all: true,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_deselect_all() {
+        let yaml = "
+                name: 'Clear languages'
+                select:
+                    element:
+                        id: 'languages'
+                    deselect_all: true
+              ";
+
+        let select = serde_yaml::from_str(yaml).unwrap();
+        let result = Select::new(&select);
+        assert_eq!(None, result.unwrap().select_by);
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+                name: 'Pick a country'
+                select:
+                    element:
+                        id: 'country'
+                    by_value: 'us'
+                    by_text: 'United States'
+              ";
+
+        let select = serde_yaml::from_str(yaml).unwrap();
+        let result = Select::new(&select);
+        let expected = Err(TaskErr {
+            message: String::from("Only one of by_value, by_text or by_index is allowed"),
+            task: Some(select),
+            task_type: Some(TaskTypes::SELECT),
+        });
+        assert_eq!(expected, result);
+
+        let yaml = "
+                name: 'Pick a country'
+                select:
+                    element:
+                        id: 'country'
+              ";
+
+        let select = serde_yaml::from_str(yaml).unwrap();
+        let result = Select::new(&select);
+        let expected = Err(TaskErr {
+            message: String::from("Expected one of by_value, by_text, by_index or deselect_all"),
+            task: Some(select),
+            task_type: Some(TaskTypes::SELECT),
+        });
+        assert_eq!(expected, result)
+    }
+}
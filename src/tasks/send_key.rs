@@ -3,10 +3,10 @@ use serde_yaml::Value;
 use std::collections::HashMap;
 use std::time::Instant;
 
-use crate::{element::Element, executor::{ExecuteResult, WebDriverSession}, variables::resolve_variables};
+use crate::{element::Element, executor::{ExecuteResult, WebDriverSession}, keys::{parse_keys, to_text}};
 
 use super::{
-    get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes,
+    get_task, get_task_name, resolve_keys, Task, TaskErr, TaskOk, TaskResult, TaskTypes,
 };
 
 const TASK_TYPE: &str = "send_key";
@@ -17,6 +17,7 @@ pub struct SendKey {
     name: String,
     element: Element,
     input: String,
+    clear: bool,
 }
 
 #[async_trait]
@@ -25,6 +26,7 @@ impl Task for SendKey {
         let name = get_task_name(task)?;
         let send_key = get_task(task, TASK_TYPE)?;
         let input = get_input(task)?;
+        let clear = get_clear(task)?;
 
         let element = match Element::new(send_key) {
             Ok(element) => element,
@@ -42,16 +44,37 @@ impl Task for SendKey {
             _task_types: TaskTypes::SENDKEY,
             element,
             input,
+            clear,
         })
     }
 
+    fn element(&self) -> Option<&Element> {
+        Some(&self.element)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
         // println!(
         //     "Taske Type: {:#?}\nName: {:#?}\nelement Type: {:#?},\nValue: {}",
         //     self._task_types, self.name, self.element.element_type, self.element.value
         // );
-        let by = Element::find_by_resolve(&self.element, &web_driver_session.variables);
+        let by = match Element::find_by_resolve(&self.element, &web_driver_session) {
+            Ok(by) => by,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::SENDKEY),
+                    },
+                ))
+            }
+        };
 
         let element = match web_driver_session.driver.find(by).await {
             Ok(element) => element,
@@ -70,7 +93,32 @@ impl Task for SendKey {
             }
         };
 
-        let input = resolve_variables(&self.input, &web_driver_session.variables);
+        let input = match resolve_keys(&self.input, &web_driver_session) {
+            Ok(keys) => to_text(&keys),
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::SENDKEY),
+                    },
+                ))
+            }
+        };
+        if self.clear {
+            if let Err(e) = element.clear().await {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: format!("Unable to clear element: {}", e),
+                        task: None,
+                        task_type: Some(TaskTypes::SENDKEY),
+                    },
+                ));
+            }
+        }
+
         let send_key = element.send_keys(input).await;
         let name = self.name.clone();
 
@@ -81,7 +129,8 @@ impl Task for SendKey {
                     name,
                     task_type: TaskTypes::SENDKEY,
                     duration: start.elapsed().as_secs(),
-                    result: None
+                    result: None,
+                    path: None
                 },
             )),
             Err(_) => {
@@ -101,6 +150,21 @@ impl Task for SendKey {
     }
 }
 
+fn get_clear(task: &HashMap<String, Value>) -> TaskResult<bool> {
+    let send_key = get_task(task, TASK_TYPE)?;
+    match send_key.get("clear") {
+        None => Ok(false),
+        Some(clear) => match clear.as_bool() {
+            Some(clear) => Ok(clear),
+            None => Err(TaskErr {
+                message: "clear is not a bool".to_string(),
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::SENDKEY),
+            }),
+        },
+    }
+}
+
 fn get_input(task: &HashMap<String, Value>) -> TaskResult<String> {
     let link = get_task(task, TASK_TYPE)?;
     let input = match link.get("input") {
@@ -135,6 +199,14 @@ fn get_input(task: &HashMap<String, Value>) -> TaskResult<String> {
         });
     }
 
+    if let Err(message) = parse_keys(&input, |_| false) {
+        return Err(TaskErr {
+            message,
+            task: Some(task.clone()),
+            task_type: Some(TaskTypes::SENDKEY),
+        });
+    }
+
     Ok(input)
 }
 
@@ -345,9 +417,11 @@ mod tests {
             _task_types: TaskTypes::SENDKEY,
             name: "enter rust in search".to_owned(),
             input: "Rust".to_owned(),
+            clear: false,
             element: Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"searchInput\"]".to_owned(),
+                frame: None,
             },
         });
         assert_eq!(expected, result)
@@ -369,9 +443,38 @@ mod tests {
             _task_types: TaskTypes::SENDKEY,
             name: "enter rust in search".to_owned(),
             input: "Rust".to_owned(),
+            clear: false,
+            element: Element {
+                element_type: ElementType::ID,
+                value: "searchInput".to_owned(),
+                frame: None,
+            },
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_clear() {
+        let yaml = "
+        name: 'replace the search'
+        send_key:
+            input: 'Rust{ENTER}'
+            clear: true
+            element:
+                id: 'searchInput'
+              ";
+
+        let send_key_task = serde_yaml::from_str(yaml).unwrap();
+        let result = SendKey::new(&send_key_task);
+        let expected = Ok(SendKey {
+            _task_types: TaskTypes::SENDKEY,
+            name: "replace the search".to_owned(),
+            input: "Rust{ENTER}".to_owned(),
+            clear: true,
             element: Element {
                 element_type: ElementType::ID,
                 value: "searchInput".to_owned(),
+                frame: None,
             },
         });
         assert_eq!(expected, result)
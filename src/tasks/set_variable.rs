@@ -4,18 +4,31 @@ use async_trait::async_trait;
 use serde_yaml::Value;
 use std::time::Instant;
 
-use crate::{executor::{ExecuteResult, WebDriverSession}};
+use crate::{
+    executor::{ExecuteResult, WebDriverSession, BUILT_IN_VARIABLES},
+    expression::Expression,
+    variables::{to_variables, Variable},
+};
 
-use super::{get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes, get_task, to_hash};
+use super::{get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes, get_task};
 
 
 const TASK_TYPE: &str = "set_vars";
+const EXPRESSION_PREFIX: char = '=';
 
+#[derive(PartialEq, Eq, Debug)]
+enum SetValue {
+    Value(Variable),
+    Expression(Expression),
+}
+
+/// Variables are set in the order they are written, so an expression can
+/// use the ones above it.
 #[derive(PartialEq, Eq, Debug)]
 pub struct SetVars {
     _task_types: TaskTypes,
     name: String,
-    variables: HashMap<String, String>
+    variables: Vec<(String, SetValue)>
 }
 
 #[async_trait]
@@ -24,7 +37,7 @@ impl Task for SetVars {
         let name = get_task_name(task)?;
         let variables = get_task(task, TASK_TYPE)?;
 
-        let variables: HashMap<String, String> = match to_hash(variables) {
+        let variables = match to_variables(variables).and_then(to_values) {
             Ok(v) => v,
             Err(e) => {
                 return Err(TaskErr {
@@ -42,12 +55,43 @@ impl Task for SetVars {
         })
     }
 
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     async fn execute(&self, mut web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
-        
-        for (key, value) in self.variables.iter() { 
-            web_driver_session.add_variable(key, value);
-         }
+
+        for (key, value) in self.variables.iter() {
+            let value = match value {
+                SetValue::Expression(expression) => {
+                    match expression.evaluate(&web_driver_session.variables) {
+                        Ok(result) => Variable::from(result),
+                        Err(e) => {
+                            return Err((
+                                web_driver_session,
+                                TaskErr {
+                                    message: format!("{}: {}", key, e),
+                                    task: None,
+                                    task_type: Some(TaskTypes::SETVARIABLE),
+                                },
+                            ))
+                        }
+                    }
+                }
+                SetValue::Value(value) => value.clone(),
+            };
+            if let Err(e) = web_driver_session.add_variable(key, value) {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::SETVARIABLE),
+                    },
+                ));
+            }
+        }
 
         let name = self.name.clone();
         return Ok((
@@ -56,12 +100,50 @@ impl Task for SetVars {
                 name,
                 task_type: TaskTypes::SETVARIABLE,
                 duration: start.elapsed().as_secs(),
-                result: None
+                result: None,
+                path: None
             },
         ));
     }
 }
 
+fn to_values(variables: Vec<(String, Variable)>) -> Result<Vec<(String, SetValue)>, String> {
+    let mut values: Vec<(String, SetValue)> = Vec::new();
+
+    for (key, value) in variables {
+        if BUILT_IN_VARIABLES.contains(&key.as_str()) {
+            return Err(format!("{}: is a built-in variable", key));
+        }
+
+        let value = match as_expression(&value) {
+            Some(expression) => match Expression::parse(expression) {
+                Ok(expression) => SetValue::Expression(expression),
+                Err(e) => return Err(format!("{}: {}", key, e)),
+            },
+            None => match value {
+                Variable::String(s) => SetValue::Value(Variable::from(s.strip_prefix(EXPRESSION_PREFIX).unwrap_or(&s))),
+                _ => SetValue::Value(value),
+            },
+        };
+        values.push((key, value));
+    }
+
+    Ok(values)
+}
+
+/// `= {price} * {qty}` is an expression, `==foo` is the literal `=foo`.
+fn as_expression(value: &Variable) -> Option<&str> {
+    let value = match value {
+        Variable::String(s) => s,
+        _ => return None,
+    };
+
+    match value.strip_prefix(EXPRESSION_PREFIX) {
+        Some(rest) if !rest.starts_with(EXPRESSION_PREFIX) => Some(rest),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,10 +274,109 @@ mod tests {
                     age: '42'
               ";
 
-        let mut variables: HashMap<String, String> = HashMap::new();      
-        variables.insert("name".to_string(), "foo".to_string());
-        variables.insert("age".to_string(), "42".to_string());
+        let variables = vec![
+            ("name".to_string(), SetValue::Value(Variable::from("foo"))),
+            ("age".to_string(), SetValue::Value(Variable::from("42"))),
+        ];
+
+
+        let variable = serde_yaml::from_str(yaml).unwrap();
+        let result = SetVars::new(&variable);
+        let expected = Ok(SetVars {
+            name: "set vars".to_string(),
+            _task_types: TaskTypes::SETVARIABLE,
+            variables
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_invalid_expression() {
+        let yaml = "
+                name: 'set vars'
+                set_vars:
+                    total: '= ({price} * 2'
+              ";
+
+        let variable = serde_yaml::from_str(yaml).unwrap();
+        let result = SetVars::new(&variable);
+        let expected = Err(TaskErr {
+            message: String::from("total: Missing ) in expression"),
+            task: Some(variable),
+            task_type: Some(TaskTypes::SETVARIABLE),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_expression() {
+        let yaml = "
+                name: 'set vars'
+                set_vars:
+                    total: '= {price} * 2'
+                    label: '==foo'
+              ";
+
+        let variable = serde_yaml::from_str(yaml).unwrap();
+        let result = SetVars::new(&variable);
+        let expected = Ok(SetVars {
+            name: "set vars".to_string(),
+            _task_types: TaskTypes::SETVARIABLE,
+            variables: vec![
+                ("total".to_string(), SetValue::Expression(Expression::parse(" {price} * 2").unwrap())),
+                ("label".to_string(), SetValue::Value(Variable::from("=foo"))),
+            ],
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_built_in_variable() {
+        let yaml = "
+                name: 'set vars'
+                set_vars:
+                    task_index: 3
+              ";
+
+        let variable = serde_yaml::from_str(yaml).unwrap();
+        let result = SetVars::new(&variable);
+        let expected = Err(TaskErr {
+            message: String::from("task_index: is a built-in variable"),
+            task: Some(variable),
+            task_type: Some(TaskTypes::SETVARIABLE),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_as_expression() {
+        assert_eq!(Some(" {price} * 2"), as_expression(&Variable::from("= {price} * 2")));
+        assert_eq!(None, as_expression(&Variable::from("==foo")));
+        assert_eq!(None, as_expression(&Variable::from("foo")));
+        assert_eq!(None, as_expression(&Variable::from(2.0)));
+    }
+
+    #[test]
+    fn test_task_typed() {
+        let yaml = "
+                name: 'set vars'
+                set_vars:
+                    count: 3
+                    active: true
+                    user:
+                        email: 'foo@bar.com'
+                    ids:
+                        - 42
+              ";
+
+        let user: Value = serde_yaml::from_str("email: 'foo@bar.com'").unwrap();
 
+        let variables = vec![
+            ("count".to_string(), SetValue::Value(Variable::from(3.0))),
+            ("active".to_string(), SetValue::Value(Variable::from(true))),
+            ("user".to_string(), SetValue::Value(Variable::try_from(&user).unwrap())),
+            ("ids".to_string(), SetValue::Value(Variable::List(vec![Variable::from(42.0)]))),
+        ];
 
         let variable = serde_yaml::from_str(yaml).unwrap();
         let result = SetVars::new(&variable);
@@ -0,0 +1,304 @@
+use async_trait::async_trait;
+use serde_json::json;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    executor::{ExecuteResult, WebDriverSession},
+    variables::Variable,
+};
+
+const LOCAL_STORAGE: &str = "local_storage";
+const SESSION_STORAGE: &str = "session_storage";
+
+/// `window.localStorage` or `window.sessionStorage` of the current page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageArea {
+    Local,
+    Session,
+}
+
+impl fmt::Display for StorageArea {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StorageArea::Local => write!(f, "localStorage"),
+            StorageArea::Session => write!(f, "sessionStorage"),
+        }
+    }
+}
+
+impl StorageArea {
+    /// Runs `script` with `storage` set to the area, WebDriver has no storage
+    /// commands. The script's own arguments start at `arguments[1]`.
+    async fn execute(
+        &self,
+        script: &str,
+        mut args: Vec<serde_json::Value>,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<serde_json::Value, String> {
+        args.insert(0, json!(self.to_string()));
+        let script = format!("const storage = window[arguments[0]]; {}", script);
+
+        match web_driver_session.driver.execute(&script, args).await {
+            Ok(ret) => Ok(ret.json().clone()),
+            Err(e) => Err(format!("Unable to use {}: {}", self, e)),
+        }
+    }
+
+    /// The value of `key`, `None` when the key is not set.
+    pub async fn get_item(
+        &self,
+        key: &str,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<Option<String>, String> {
+        let script = "return storage.getItem(arguments[1]);";
+        match self
+            .execute(script, vec![json!(key)], web_driver_session)
+            .await?
+        {
+            serde_json::Value::String(value) => Ok(Some(value)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum StorageAction {
+    Set(String, String),
+    Get(String, String),
+    Remove(String),
+    Clear,
+}
+
+/// Sets, reads and removes web storage keys, like feature flags or tokens.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Storage {
+    _task_types: TaskTypes,
+    name: String,
+    area: StorageArea,
+    action: StorageAction,
+}
+
+#[async_trait]
+impl Task for Storage {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Storage> {
+        let name = get_task_name(task)?;
+
+        let (task_type, area, storage) = if task.contains_key(SESSION_STORAGE) {
+            let storage = get_task(task, SESSION_STORAGE)?;
+            (TaskTypes::SESSIONSTORAGE, StorageArea::Session, storage)
+        } else {
+            let storage = get_task(task, LOCAL_STORAGE)?;
+            (TaskTypes::LOCALSTORAGE, StorageArea::Local, storage)
+        };
+
+        match get_action(storage) {
+            Ok(action) => Ok(Storage {
+                _task_types: task_type,
+                name,
+                area,
+                action,
+            }),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(task_type),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, mut web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.perform(&mut web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(self._task_types),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: self._task_types,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Storage {
+    async fn perform(&self, web_driver_session: &mut WebDriverSession) -> Result<(), String> {
+        let area = self.area;
+
+        match &self.action {
+            StorageAction::Set(key, value) => {
+                let key = web_driver_session.resolve(key)?;
+                let value = web_driver_session.resolve(value)?;
+                let script = "storage.setItem(arguments[1], arguments[2]);";
+                area.execute(script, vec![json!(key), json!(value)], web_driver_session)
+                    .await?;
+            }
+            StorageAction::Get(key, save_as) => {
+                let key = web_driver_session.resolve(key)?;
+                let value = match area.get_item(&key, web_driver_session).await? {
+                    Some(value) => value,
+                    None => return Err(format!("{} key [{}] not found", area, key)),
+                };
+                web_driver_session.add_variable(save_as, Variable::from(value))?;
+            }
+            StorageAction::Remove(key) => {
+                let key = web_driver_session.resolve(key)?;
+                let script = "storage.removeItem(arguments[1]);";
+                area.execute(script, vec![json!(key)], web_driver_session)
+                    .await?;
+            }
+            StorageAction::Clear => {
+                area.execute("storage.clear();", Vec::new(), web_driver_session)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn get_action(storage: &Mapping) -> Result<StorageAction, String> {
+    let action = match storage.get("action").map(|action| action.as_str()) {
+        Some(Some(action)) => action,
+        Some(None) => return Err("action field is not a string".to_string()),
+        None => return Err("action field not found".to_string()),
+    };
+
+    match action {
+        "set" => Ok(StorageAction::Set(
+            get_string(storage, "key")?,
+            get_string(storage, "value")?,
+        )),
+        "get" => Ok(StorageAction::Get(
+            get_string(storage, "key")?,
+            get_string(storage, "save_as")?,
+        )),
+        "remove" => Ok(StorageAction::Remove(get_string(storage, "key")?)),
+        "clear" => Ok(StorageAction::Clear),
+        _ => Err(format!("Unknown storage action: {}", action)),
+    }
+}
+
+/// Numbers and bools are stored as text, `value: true` sets `"true"`.
+fn get_string(storage: &Mapping, key: &str) -> Result<String, String> {
+    match storage.get(key) {
+        Some(Value::String(value)) => Ok(value.clone()),
+        Some(Value::Number(value)) => Ok(value.to_string()),
+        Some(Value::Bool(value)) => Ok(value.to_string()),
+        Some(_) => Err(format!("{} field is not a string", key)),
+        None => Err(format!("{} field not found", key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(yaml: &str) -> Result<StorageAction, String> {
+        let task = serde_yaml::from_str(yaml).unwrap();
+        Storage::new(&task)
+            .map(|storage| storage.action)
+            .map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Storage::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'enable the new checkout'
+        local_storage:
+            action: set
+            key: 'new_checkout'
+            value: true
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Storage::new(&task);
+        let expected = Ok(Storage {
+            _task_types: TaskTypes::LOCALSTORAGE,
+            name: "enable the new checkout".to_owned(),
+            area: StorageArea::Local,
+            action: StorageAction::Set("new_checkout".to_owned(), "true".to_owned()),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_session_storage() {
+        let yaml = "
+        name: 'read the token'
+        session_storage:
+            action: get
+            key: 'token'
+            save_as: 'token'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Storage::new(&task).unwrap();
+        assert_eq!(TaskTypes::SESSIONSTORAGE, result._task_types);
+        assert_eq!(StorageArea::Session, result.area);
+        assert_eq!(
+            StorageAction::Get("token".to_owned(), "token".to_owned()),
+            result.action
+        );
+
+        let yaml = "
+        name: 'log out'
+        session_storage:
+            action: clear
+              ";
+        assert_eq!(Ok(StorageAction::Clear), action(yaml));
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+        name: 'read the token'
+        local_storage:
+            action: get
+            key: 'token'
+              ";
+        let expected = Err("save_as field not found".to_string());
+        assert_eq!(expected, action(yaml));
+
+        let yaml = "
+        name: 'read the token'
+        local_storage:
+            action: read
+              ";
+        let expected = Err("Unknown storage action: read".to_string());
+        assert_eq!(expected, action(yaml));
+    }
+}
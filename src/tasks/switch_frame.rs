@@ -0,0 +1,260 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::time::Instant;
+use thirtyfour::By;
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    element::{xpath_literal, Element},
+    executor::{ExecuteResult, WebDriverSession},
+};
+
+const SWITCH_FRAME: &str = "switch_frame";
+const SWITCH_TO_PARENT: &str = "switch_to_parent";
+const DEFAULT_CONTENT: &str = "default_content";
+
+#[derive(Debug, PartialEq, Eq)]
+enum FrameTarget {
+    Element(Element),
+    Index(u16),
+    Name(String),
+    Parent,
+    Default,
+}
+
+/// Moves later tasks into an iframe, or back out of it. Unlike the `frame`
+/// key of an element, the switch lasts until the next frame task.
+#[derive(PartialEq, Eq, Debug)]
+pub struct SwitchFrame {
+    _task_types: TaskTypes,
+    name: String,
+    target: FrameTarget,
+}
+
+#[async_trait]
+impl Task for SwitchFrame {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<SwitchFrame> {
+        let name = get_task_name(task)?;
+
+        let (task_type, target) = if task.contains_key(SWITCH_TO_PARENT) {
+            (TaskTypes::SWITCHTOPARENT, Ok(FrameTarget::Parent))
+        } else if task.contains_key(DEFAULT_CONTENT) {
+            (TaskTypes::DEFAULTCONTENT, Ok(FrameTarget::Default))
+        } else {
+            let switch_frame = get_task(task, SWITCH_FRAME)?;
+            (TaskTypes::SWITCHFRAME, get_target(switch_frame))
+        };
+
+        match target {
+            Ok(target) => Ok(SwitchFrame {
+                _task_types: task_type,
+                name,
+                target,
+            }),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(task_type),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.switch(&web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(self._task_types),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: self._task_types,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl SwitchFrame {
+    async fn switch(&self, web_driver_session: &WebDriverSession) -> Result<(), String> {
+        let driver = &web_driver_session.driver;
+
+        let by: By = match &self.target {
+            FrameTarget::Parent => {
+                return driver.enter_parent_frame().await.map_err(|e| e.to_string())
+            }
+            FrameTarget::Default => {
+                return driver
+                    .enter_default_frame()
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            FrameTarget::Index(index) => {
+                return match driver.enter_frame(*index).await {
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(format!("Unable to switch to frame [{}]: {}", index, e)),
+                }
+            }
+            FrameTarget::Name(name) => {
+                // Like Selenium, a frame name also matches its id.
+                let name = xpath_literal(&web_driver_session.resolve(name)?);
+                By::XPath(&format!(
+                    "(//iframe|//frame)[@name={0} or @id={0}]",
+                    name
+                ))
+            }
+            FrameTarget::Element(element) => {
+                Element::enter_frames(element, web_driver_session).await?;
+                Element::find_by_resolve(element, web_driver_session)?
+            }
+        };
+
+        let entered = match driver.find(by).await {
+            Ok(frame) => frame.enter_frame().await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match entered {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Unable to switch to frame: {}", e)),
+        }
+    }
+}
+
+fn get_target(switch_frame: &Mapping) -> Result<FrameTarget, String> {
+    let keys: Vec<&str> = ["element", "index", "name"]
+        .into_iter()
+        .filter(|key| switch_frame.contains_key(*key))
+        .collect();
+
+    match keys.as_slice() {
+        ["element"] => Ok(FrameTarget::Element(Element::new(switch_frame)?)),
+        ["index"] => match switch_frame.get("index").and_then(|index| index.as_u64()) {
+            Some(index) if index <= u16::MAX as u64 => Ok(FrameTarget::Index(index as u16)),
+            _ => Err("index field is not a number".to_string()),
+        },
+        ["name"] => match switch_frame.get("name").and_then(|name| name.as_str()) {
+            Some(name) => Ok(FrameTarget::Name(name.to_string())),
+            None => Err("name field is not a string".to_string()),
+        },
+        [] => Err("Expected one of element, index or name".to_string()),
+        _ => Err("Only one of element, index or name is allowed".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    fn target(yaml: &str) -> Result<FrameTarget, String> {
+        let task = serde_yaml::from_str(yaml).unwrap();
+        SwitchFrame::new(&task)
+            .map(|switch_frame| switch_frame.target)
+            .map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = SwitchFrame::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_element() {
+        let yaml = "
+        name: 'enter the payment form'
+        switch_frame:
+            element:
+                id: 'payment'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = SwitchFrame::new(&task);
+        let expected = Ok(SwitchFrame {
+            _task_types: TaskTypes::SWITCHFRAME,
+            name: "enter the payment form".to_owned(),
+            target: FrameTarget::Element(Element {
+                element_type: ElementType::ID,
+                value: "payment".to_owned(),
+                frame: None,
+            }),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_index_and_name() {
+        let yaml = "
+        name: 'enter the first frame'
+        switch_frame:
+            index: 0
+              ";
+        assert_eq!(Ok(FrameTarget::Index(0)), target(yaml));
+
+        let yaml = "
+        name: 'enter the editor'
+        switch_frame:
+            name: 'editor'
+              ";
+        assert_eq!(Ok(FrameTarget::Name("editor".to_owned())), target(yaml));
+    }
+
+    #[test]
+    fn test_task_parent_and_default() {
+        let yaml = "
+        name: 'leave the editor'
+        switch_to_parent: true
+              ";
+        assert_eq!(Ok(FrameTarget::Parent), target(yaml));
+
+        let yaml = "
+        name: 'back to the page'
+        default_content: true
+              ";
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = SwitchFrame::new(&task);
+        let expected = Ok(SwitchFrame {
+            _task_types: TaskTypes::DEFAULTCONTENT,
+            name: "back to the page".to_owned(),
+            target: FrameTarget::Default,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_invalid() {
+        let yaml = "
+        name: 'enter the editor'
+        switch_frame:
+            index: 0
+            name: 'editor'
+              ";
+        let expected = Err("Only one of element, index or name is allowed".to_string());
+        assert_eq!(expected, target(yaml));
+    }
+}
@@ -0,0 +1,262 @@
+mod remote;
+
+use std::{collections::HashMap, fs, path::Path};
+
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::time::Instant;
+use thirtyfour::By;
+
+use crate::{
+    element::Element,
+    executor::{ExecuteResult, WebDriverSession},
+    variables::{has_variables, resolve_variables},
+};
+
+use self::remote::{is_local, RemoteUpload};
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+
+const TASK_TYPE: &str = "upload";
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Upload {
+    _task_types: TaskTypes,
+    name: String,
+    element: Element,
+    files: Vec<String>,
+}
+
+#[async_trait]
+impl Task for Upload {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Upload> {
+        let name = get_task_name(task)?;
+        let upload = get_task(task, TASK_TYPE)?;
+
+        match get_upload(name, upload) {
+            Ok(upload) => Ok(upload),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::UPLOAD),
+            }),
+        }
+    }
+
+    fn element(&self) -> Option<&Element> {
+        Some(&self.element)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.upload(&web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(TaskTypes::UPLOAD),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::UPLOAD,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Upload {
+    async fn upload(&self, web_driver_session: &WebDriverSession) -> Result<(), String> {
+        let driver = &web_driver_session.driver;
+        let by: By = Element::find_by_resolve(&self.element, web_driver_session)?;
+        let element = match driver.find(by).await {
+            Ok(element) => element,
+            Err(e) => return Err(format!("{}", e)),
+        };
+
+        let remote = match is_local(&web_driver_session.server_url) {
+            true => None,
+            false => match driver.session_id().await {
+                Ok(session_id) => Some(RemoteUpload::new(
+                    &web_driver_session.server_url,
+                    &session_id.to_string(),
+                )),
+                Err(e) => return Err(e.to_string()),
+            },
+        };
+
+        let mut paths: Vec<String> = Vec::new();
+        for file in &self.files {
+            let file = web_driver_session.resolve(file)?;
+            // Browsers need absolute paths.
+            let path = match fs::canonicalize(&file) {
+                Ok(path) => path,
+                Err(_) => return Err(format!("File [{}] not found", file)),
+            };
+
+            let uploaded = match &remote {
+                Some(remote) => remote.upload_file(&path).await?,
+                None => None,
+            };
+            paths.push(uploaded.unwrap_or_else(|| path.to_string_lossy().to_string()));
+        }
+
+        // A file input takes several files separated by new lines.
+        match element.send_keys(paths.join("\n")).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Unable to upload files: {}", e)),
+        }
+    }
+}
+
+fn get_upload(name: String, upload: &Mapping) -> Result<Upload, String> {
+    let element = Element::new(upload)?;
+
+    let files = match upload.get("files") {
+        Some(Value::String(file)) => vec![file.clone()],
+        Some(Value::Sequence(files)) => {
+            let mut paths: Vec<String> = Vec::new();
+            for file in files {
+                match file.as_str() {
+                    Some(file) => paths.push(file.to_string()),
+                    None => return Err("files - path is not a string".to_string()),
+                }
+            }
+            paths
+        }
+        Some(_) => return Err("files field is not a string or a list".to_string()),
+        None => return Err("files field not found".to_string()),
+    };
+
+    if files.is_empty() {
+        return Err("files - list is empty".to_string());
+    }
+
+    // Paths with variables are checked once resolved.
+    for file in files.iter().filter(|file| !has_variables(file)) {
+        let file = resolve_variables(file, &HashMap::new());
+        if !Path::new(&file).is_file() {
+            return Err(format!("File [{}] not found", file));
+        }
+    }
+
+    Ok(Upload {
+        _task_types: TaskTypes::UPLOAD,
+        name,
+        element,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Upload::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'attach the report'
+        upload:
+            element:
+                id: 'attachment'
+            files:
+                - 'Cargo.toml'
+                - '{report}'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Upload::new(&task);
+        let expected = Ok(Upload {
+            _task_types: TaskTypes::UPLOAD,
+            name: "attach the report".to_owned(),
+            element: Element {
+                element_type: ElementType::ID,
+                value: "attachment".to_owned(),
+                frame: None,
+            },
+            files: vec!["Cargo.toml".to_owned(), "{report}".to_owned()],
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_file_with_braces() {
+        let path = std::env::temp_dir().join(format!("ls-upload-{{{}}}.txt", std::process::id()));
+        std::fs::write(&path, "report").unwrap();
+
+        let yaml = format!(
+            "
+        name: 'attach the report'
+        upload:
+            element:
+                id: 'attachment'
+            files: '{}'
+              ",
+            path.display()
+        );
+
+        let task = serde_yaml::from_str(&yaml).unwrap();
+        let result = Upload::new(&task).map(|upload| upload.files);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Ok(vec![path.display().to_string()]), result.map_err(|e| e.message));
+
+        let yaml = "
+        name: 'attach the report'
+        upload:
+            element:
+                id: 'attachment'
+            files: 'missing/{2023}.pdf'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Upload::new(&task).map_err(|e| e.message);
+        assert_eq!(Err("File [missing/{2023}.pdf] not found".to_string()), result.map(|_| ()));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let yaml = "
+        name: 'attach the report'
+        upload:
+            element:
+                id: 'attachment'
+            files: 'missing/report.pdf'
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Upload::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("File [missing/report.pdf] not found"),
+            task: Some(task),
+            task_type: Some(TaskTypes::UPLOAD),
+        });
+        assert_eq!(expected, result)
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{Cursor, Write};
+use std::net::IpAddr;
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hyper::{body, client::HttpConnector, Body, Client, Request, StatusCode, Uri};
+use hyper_rustls::HttpsConnector;
+use serde_json::{json, Value};
+use zip::{write::FileOptions, ZipWriter};
+
+type HttpsClient = Client<HttpsConnector<HttpConnector>>;
+
+/// A server on this machine reads the files itself, a remote one like a
+/// Selenium Grid needs them copied first.
+pub fn is_local(server_url: &str) -> bool {
+    let host = match server_url.parse::<Uri>() {
+        Ok(uri) => uri
+            .host()
+            .map(|host| host.trim_matches(|c| c == '[' || c == ']').to_string()),
+        Err(_) => None,
+    };
+
+    match host {
+        Some(host) if host.eq_ignore_ascii_case("localhost") => true,
+        Some(host) => host
+            .parse::<IpAddr>()
+            .map(|ip| ip.is_loopback())
+            .unwrap_or(false),
+        None => false,
+    }
+}
+
+/// Copies files to a Selenium server with its `se/file` endpoint.
+pub struct RemoteUpload {
+    client: HttpsClient,
+    url: String,
+}
+
+impl RemoteUpload {
+    pub fn new(server_url: &str, session_id: &str) -> RemoteUpload {
+        let connector = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .https_or_http()
+            .enable_http1()
+            .build();
+
+        RemoteUpload {
+            client: Client::builder().build(connector),
+            url: format!(
+                "{}/session/{}/se/file",
+                server_url.trim_end_matches('/'),
+                session_id
+            ),
+        }
+    }
+
+    /// Returns the path of `path` on the server, `None` when the server has
+    /// no such endpoint and reads the file itself.
+    pub async fn upload_file(&self, path: &Path) -> Result<Option<String>, String> {
+        let body = json!({ "file": STANDARD.encode(zip_file(path)?) }).to_string();
+
+        let request = match Request::post(&self.url)
+            .header("Content-Type", "application/json; charset=utf-8")
+            .body(Body::from(body))
+        {
+            Ok(request) => request,
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let response = match self.client.request(request).await {
+            Ok(response) => response,
+            Err(e) => return Err(format!("Unable to upload [{}]: {}", path.display(), e)),
+        };
+
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND || status == StatusCode::METHOD_NOT_ALLOWED {
+            return Ok(None);
+        }
+
+        let bytes = match body::to_bytes(response.into_body()).await {
+            Ok(bytes) => bytes,
+            Err(e) => return Err(e.to_string()),
+        };
+        let response: Value = match serde_json::from_slice(&bytes) {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(format!(
+                    "Unable to upload [{}]: {} invalid response: {}",
+                    path.display(),
+                    status,
+                    e
+                ))
+            }
+        };
+
+        match (status.is_success(), response["value"].as_str()) {
+            (true, Some(remote)) => Ok(Some(remote.to_string())),
+            _ => Err(format!(
+                "Unable to upload [{}]: {} {}",
+                path.display(),
+                status,
+                response["value"]["message"].as_str().unwrap_or_default()
+            )),
+        }
+    }
+}
+
+/// The endpoint expects a zip holding the single file.
+fn zip_file(path: &Path) -> Result<Vec<u8>, String> {
+    let content = match std::fs::read(path) {
+        Ok(content) => content,
+        Err(e) => return Err(format!("Unable to read [{}]: {}", path.display(), e)),
+    };
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => return Err(format!("[{}] is not a file", path.display())),
+    };
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let written = zip
+        .start_file(name, FileOptions::default())
+        .and_then(|_| zip.write_all(&content).map_err(|e| e.into()))
+        .and_then(|_| zip.finish());
+
+    match written {
+        Ok(zip) => Ok(zip.into_inner()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_is_local() {
+        assert!(is_local("http://localhost:4444"));
+        assert!(is_local("http://127.0.0.1:9515/wd/hub"));
+        assert!(is_local("http://[::1]:4444"));
+        assert!(!is_local("http://selenium-hub:4444"));
+        assert!(!is_local("https://grid.example.com/wd/hub"));
+    }
+
+    #[test]
+    fn test_zip_file() {
+        let path = std::env::temp_dir().join(format!("ls-upload-{}.txt", std::process::id()));
+        std::fs::write(&path, "report").unwrap();
+
+        let zip = zip_file(&path).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(zip)).unwrap();
+        let mut file = archive.by_index(0).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        assert_eq!(path.file_name().unwrap().to_str().unwrap(), file.name());
+        assert_eq!("report", content);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
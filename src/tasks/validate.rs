@@ -2,31 +2,82 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use serde_yaml::{Mapping, Value};
+use std::fmt;
 use std::time::Instant;
 use thirtyfour::WebElement;
+use tokio::time::{sleep, Duration};
 
 use crate::{
     element::Element,
-    executor::{ExecuteResult, WebDriverSession}, variables::resolve_variables,
+    executor::{ExecuteResult, OnFail, WebDriverSession},
+    matcher::Matcher,
 };
 
-use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes, ValidationResult, ValidationReultType, to_hash};
+use super::storage::StorageArea;
+use super::{check, failed_validations, get_on_fail, get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes, ValidationResult, ValidationReultType};
 
 const TASK_TYPE: &str = "validate";
+const DEFAULT_TIMEOUT_MS: u64 = 10000;
+const POLL_MS: u64 = 250;
 #[derive(PartialEq, Eq, Debug)]
 pub enum ValidateTypes {
-    Text(String),
-    InnerHtml(String),
-    Css(HashMap<String, String>),
-    Property(HashMap<String, String>),
+    Text(Matcher),
+    InnerHtml(Matcher),
+    Css(HashMap<String, Matcher>),
+    Property(HashMap<String, Matcher>),
+    Attribute(HashMap<String, Matcher>),
+    State(ElementState, bool),
+    Absent(bool),
+    Count(Matcher),
+    Url(Matcher),
+    Title(Matcher),
+    Source(Matcher),
+    Cookie(HashMap<String, Matcher>),
+    Storage(StorageArea, HashMap<String, Matcher>),
+}
+
+impl ValidateTypes {
+    /// Page expectations don't need an element.
+    fn is_page(&self) -> bool {
+        matches!(
+            self,
+            ValidateTypes::Url(_)
+                | ValidateTypes::Title(_)
+                | ValidateTypes::Source(_)
+                | ValidateTypes::Cookie(_)
+                | ValidateTypes::Storage(_, _)
+        )
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ElementState {
+    Displayed,
+    Enabled,
+    Selected,
+    Clickable,
+}
+
+impl fmt::Display for ElementState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ElementState::Displayed => write!(f, "displayed"),
+            ElementState::Enabled => write!(f, "enabled"),
+            ElementState::Selected => write!(f, "selected"),
+            ElementState::Clickable => write!(f, "clickable"),
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
 pub struct Validate {
     _task_types: TaskTypes,
     name: String,
-    element: Element,
+    element: Option<Element>,
     expects: Vec<ValidateTypes>,
+    on_fail: Option<OnFail>,
+    /// How long `absent` waits for the element to go away, or to show up.
+    timeout: Duration,
 }
 
 #[async_trait]
@@ -35,50 +86,89 @@ impl Task for Validate {
         let name = get_task_name(task)?;
         let validate = get_task(task, TASK_TYPE)?;
 
-        let element = match Element::new(validate) {
-            Ok(element) => element,
-            Err(err) => {
+        let expects = get_expects(task)?;
+
+        // Without an element only page expectations can be checked.
+        let page_only = !validate.contains_key("element") && expects.iter().all(|e| e.is_page());
+        let element = match page_only {
+            true => None,
+            false => match Element::new(validate) {
+                Ok(element) => Some(element),
+                Err(err) => {
+                    return Err(TaskErr {
+                        message: err,
+                        task: Some(task.clone()),
+                        task_type: Some(TaskTypes::CLICK),
+                    })
+                }
+            },
+        };
+
+        let on_fail = match get_on_fail(validate) {
+            Ok(on_fail) => on_fail,
+            Err(e) => {
                 return Err(TaskErr {
-                    message: err,
+                    message: e,
                     task: Some(task.clone()),
-                    task_type: Some(TaskTypes::CLICK),
+                    task_type: Some(TaskTypes::VALIDATE),
                 })
             }
         };
 
-        let expects = get_expects(task)?;
+        let timeout = match validate.get("timeout") {
+            None => DEFAULT_TIMEOUT_MS,
+            Some(timeout) => match timeout.as_u64() {
+                Some(timeout) => timeout,
+                None => {
+                    return Err(TaskErr {
+                        message: "timeout - value is not a number".to_string(),
+                        task: Some(task.clone()),
+                        task_type: Some(TaskTypes::VALIDATE),
+                    })
+                }
+            },
+        };
 
         Ok(Validate {
             _task_types: TaskTypes::VALIDATE,
             name,
             element,
             expects,
+            on_fail,
+            timeout: Duration::from_millis(timeout),
         })
     }
 
+    fn element(&self) -> Option<&Element> {
+        self.element.as_ref()
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
 
-        let web_element = match web_driver_session
-            .driver
-            .find(Element::find_by(&self.element))
-            .await
-        {
-            Ok(element) => element,
-            Err(e) => {
-                return Err((
-                    web_driver_session,
-                    TaskErr {
-                        message: format!("{}", e),
-                        task: None,
-                        task_type: Some(TaskTypes::VALIDATE),
-                    },
-                ));
-            }
+        let web_elements = match &self.element {
+            Some(element) => match self.find_elements(element, &web_driver_session).await {
+                Ok(web_elements) => web_elements,
+                Err(e) => {
+                    return Err((
+                        web_driver_session,
+                        TaskErr {
+                            message: e,
+                            task: None,
+                            task_type: Some(TaskTypes::VALIDATE),
+                        },
+                    ));
+                }
+            },
+            None => Vec::new(),
         };
 
         let results =
-            match validate(&self.expects, web_element, &web_driver_session.variables).await {
+            match validate(&self.expects, web_elements, &web_driver_session).await {
                 Ok(r) => r,
                 Err(e) => {
                     return Err((
@@ -92,6 +182,19 @@ impl Task for Validate {
                 }
             };
 
+        if self.on_fail.unwrap_or(web_driver_session.on_fail) == OnFail::STOP {
+            if let Some(message) = failed_validations(&results) {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message,
+                        task: None,
+                        task_type: Some(TaskTypes::VALIDATE),
+                    },
+                ));
+            }
+        }
+
         let name = self.name.clone();
         return Ok((
             web_driver_session,
@@ -100,32 +203,120 @@ impl Task for Validate {
                 task_type: TaskTypes::VALIDATE,
                 duration: start.elapsed().as_secs(),
                 result: Some(results),
+                path: None,
             },
         ));
     }
 }
 
+impl Validate {
+    async fn find_elements(
+        &self,
+        element: &Element,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<Vec<WebElement>, String> {
+        let by = Element::find_by_resolve(element, web_driver_session)?;
+
+        // `absent` and `count` need the lookup to succeed when nothing is found.
+        let find_all = self
+            .expects
+            .iter()
+            .any(|e| matches!(e, ValidateTypes::Absent(_) | ValidateTypes::Count(_)));
+
+        if !find_all {
+            return match web_driver_session.driver.find(by).await {
+                Ok(element) => Ok(vec![element]),
+                Err(e) => Err(format!("{}", e)),
+            };
+        }
+
+        let absent = self.expects.iter().find_map(|e| match e {
+            ValidateTypes::Absent(expect) => Some(*expect),
+            _ => None,
+        });
+
+        // `absent` waits for the page to catch up, like a message fading out.
+        let start = Instant::now();
+        loop {
+            let elements = match web_driver_session.driver.find_all(by.clone()).await {
+                Ok(elements) => elements,
+                Err(e) => return Err(format!("{}", e)),
+            };
+
+            match absent {
+                Some(absent) if elements.is_empty() != absent && start.elapsed() < self.timeout => {
+                    sleep(Duration::from_millis(POLL_MS)).await
+                }
+                _ => return Ok(elements),
+            }
+        }
+    }
+}
+
 async fn validate(
     expects: &Vec<ValidateTypes>,
-    web_element: WebElement,
-    variables: &HashMap<String, String>,
+    web_elements: Vec<WebElement>,
+    web_driver_session: &WebDriverSession,
 ) -> Result<Vec<ValidationResult>, String> {
     let mut results: Vec<ValidationResult> = Vec::new();
 
     for expect in expects {
+        match expect {
+            ValidateTypes::Absent(expect) => {
+                results.push(validate_absent(*expect, web_elements.is_empty()));
+                continue;
+            }
+            ValidateTypes::Count(expect) => {
+                let actual = web_elements.len().to_string();
+                results.push(check("Count", expect, &actual, web_driver_session));
+                continue;
+            }
+            ValidateTypes::Url(_) | ValidateTypes::Title(_) | ValidateTypes::Source(_) => {
+                results.push(validate_page(expect, web_driver_session).await);
+                continue;
+            }
+            ValidateTypes::Cookie(expect) => {
+                results.append(&mut validate_cookie(expect, web_driver_session).await);
+                continue;
+            }
+            ValidateTypes::Storage(area, expect) => {
+                results.append(&mut validate_storage(*area, expect, web_driver_session).await);
+                continue;
+            }
+            _ => {}
+        }
+
+        let web_element = match web_elements.first() {
+            Some(web_element) => web_element,
+            None => {
+                results.push(ValidationResult {
+                    validation: ValidationReultType::FAILED,
+                    message: "Failed: Element not found".to_string(),
+                });
+                continue;
+            }
+        };
+
         match expect {
             ValidateTypes::Text(expect) => {
-                results.push(validate_text(expect, &web_element, variables).await)
+                results.push(validate_text(expect, web_element, web_driver_session).await)
             }
             ValidateTypes::InnerHtml(expect) => {
-                results.push(validate_inner_html(expect, &web_element).await)
+                results.push(validate_inner_html(expect, web_element, web_driver_session).await)
             }
             ValidateTypes::Css(expect) => {
-                results.append(&mut validate_css(expect, &web_element, variables).await);
+                results.append(&mut validate_css(expect, web_element, web_driver_session).await);
             }
             ValidateTypes::Property(expect) => {
-                results.append(&mut validate_property(expect, &web_element, variables).await)
+                results.append(&mut validate_property(expect, web_element, web_driver_session).await)
+            }
+            ValidateTypes::Attribute(expect) => {
+                results.append(&mut validate_attribute(expect, web_element, web_driver_session).await)
+            }
+            ValidateTypes::State(state, expect) => {
+                results.push(validate_state(*state, *expect, web_element).await)
             }
+            _ => continue,
         }
     }
 
@@ -133,12 +324,10 @@ async fn validate(
 }
 
 async fn validate_text(
-    expect: &str,
+    expect: &Matcher,
     web_element: &WebElement,
-    variables: &HashMap<String, String>,
+    web_driver_session: &WebDriverSession,
 ) -> ValidationResult {
-    let expect = resolve_variables(expect, variables);
-
     let actual: String = match web_element.text().await {
         Ok(s) => s,
         Err(e) => {
@@ -149,19 +338,14 @@ async fn validate_text(
         }
     };
 
-    if actual.eq(&expect) {
-        return ValidationResult {
-            validation: ValidationReultType::SUCCESS,
-            message: format!("Pass: Text is {}", expect),
-        };
-    }
-    ValidationResult {
-        validation: ValidationReultType::FAILED,
-        message: format!("Failed: Text expected: [{}], actual: [{}]", expect, actual),
-    }
+    check("Text", expect, &actual, web_driver_session)
 }
 
-async fn validate_inner_html(expect: &String, web_element: &WebElement) -> ValidationResult {
+async fn validate_inner_html(
+    expect: &Matcher,
+    web_element: &WebElement,
+    web_driver_session: &WebDriverSession,
+) -> ValidationResult {
     let actual: String = match web_element.inner_html().await {
         Ok(s) => s,
         Err(e) => {
@@ -172,26 +356,13 @@ async fn validate_inner_html(expect: &String, web_element: &WebElement) -> Valid
         }
     };
 
-    if actual.eq(expect) {
-        return ValidationResult {
-            validation: ValidationReultType::SUCCESS,
-            message: format!("Pass: InnerHtml is {}", expect),
-        };
-    }
-
-    ValidationResult {
-        validation: ValidationReultType::FAILED,
-        message: format!(
-            "Failed: InnerHtml expected: [{}], actual: [{}]",
-            expect, actual
-        ),
-    }
+    check("InnerHtml", expect, &actual, web_driver_session)
 }
 
 async fn validate_css(
-    expected: &HashMap<String, String>,
+    expected: &HashMap<String, Matcher>,
     web_element: &WebElement,
-    variables: &HashMap<String, String>,
+    web_driver_session: &WebDriverSession,
 ) -> Vec<ValidationResult> {
     let mut results: Vec<ValidationResult> = Vec::new();
 
@@ -207,31 +378,17 @@ async fn validate_css(
             }
         };
 
-        let expect = resolve_variables(expect, variables);
-        if actual.eq(&expect) {
-            results.push(ValidationResult {
-                validation: ValidationReultType::SUCCESS,
-                message: format!("Pass: CSS [{}] is [{}]", css_value, expect),
-            });
-            continue;
-        }
-
-        results.push(ValidationResult {
-            validation: ValidationReultType::FAILED,
-            message: format!(
-                "Failed: CSS [{}] was [{}] expect [{}]",
-                css_value, actual, expect
-            ),
-        })
+        let label = format!("CSS [{}]", css_value);
+        results.push(check(&label, expect, &actual, web_driver_session));
     }
 
     results
 }
 
 async fn validate_property(
-    expected: &HashMap<String, String>,
+    expected: &HashMap<String, Matcher>,
     web_element: &WebElement,
-    variables: &HashMap<String, String>,
+    web_driver_session: &WebDriverSession,
 ) -> Vec<ValidationResult> {
     let mut results: Vec<ValidationResult> = Vec::new();
 
@@ -247,34 +404,179 @@ async fn validate_property(
             }
         };
 
-        let expect = resolve_variables(expect, variables);
         if let Some(actual) = prop_value {
-            if actual.eq(&expect) {
+            let label = format!("property {}", prop);
+            results.push(check(&label, expect, &actual, web_driver_session));
+            continue;
+        }
+
+        results.push(ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: format!("Failed: property [{}] not found", prop),
+        })
+    }
+
+    results
+}
+
+async fn validate_attribute(
+    expected: &HashMap<String, Matcher>,
+    web_element: &WebElement,
+    web_driver_session: &WebDriverSession,
+) -> Vec<ValidationResult> {
+    let mut results: Vec<ValidationResult> = Vec::new();
+
+    for (attribute, expect) in expected {
+        let attribute_value: Option<String> = match web_element.attr(attribute).await {
+            Ok(attribute) => attribute,
+            Err(e) => {
                 results.push(ValidationResult {
-                    validation: ValidationReultType::SUCCESS,
-                    message: format!("Pass: property {} is {}", prop, expect),
+                    validation: ValidationReultType::FAILED,
+                    message: format!("Failed: attribute error {}", e),
                 });
                 continue;
             }
-            results.push(ValidationResult {
-                validation: ValidationReultType::FAILED,
-                message: format!(
-                    "Failed: property [{}] was [{}] expected [{}]",
-                    prop, actual, expect
-                ),
-            });
+        };
+
+        if let Some(actual) = attribute_value {
+            let label = format!("attribute {}", attribute);
+            results.push(check(&label, expect, &actual, web_driver_session));
             continue;
         }
 
         results.push(ValidationResult {
             validation: ValidationReultType::FAILED,
-            message: format!("Failed: property [{}] not found", prop),
+            message: format!("Failed: attribute [{}] not found", attribute),
         })
     }
 
     results
 }
 
+async fn validate_page(
+    expect: &ValidateTypes,
+    web_driver_session: &WebDriverSession,
+) -> ValidationResult {
+    let driver = &web_driver_session.driver;
+    let (label, expect, actual) = match expect {
+        ValidateTypes::Url(expect) => ("Url", expect, driver.current_url().await.map(|u| u.to_string())),
+        ValidateTypes::Title(expect) => ("Title", expect, driver.title().await),
+        ValidateTypes::Source(expect) => ("Source", expect, driver.source().await),
+        _ => {
+            return ValidationResult {
+                validation: ValidationReultType::FAILED,
+                message: "Failed: Not a page validation".to_string(),
+            }
+        }
+    };
+
+    match actual {
+        Ok(actual) => check(label, expect, &actual, web_driver_session),
+        Err(e) => ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: format!("Failed: {} error {}", label, e),
+        },
+    }
+}
+
+async fn validate_cookie(
+    expected: &HashMap<String, Matcher>,
+    web_driver_session: &WebDriverSession,
+) -> Vec<ValidationResult> {
+    let mut results: Vec<ValidationResult> = Vec::new();
+
+    for (name, expect) in expected {
+        match web_driver_session.driver.get_named_cookie(name).await {
+            Ok(cookie) => {
+                let label = format!("cookie {}", name);
+                results.push(check(&label, expect, cookie.value(), web_driver_session));
+            }
+            Err(_) => results.push(ValidationResult {
+                validation: ValidationReultType::FAILED,
+                message: format!("Failed: cookie [{}] not found", name),
+            }),
+        }
+    }
+
+    results
+}
+
+async fn validate_storage(
+    area: StorageArea,
+    expected: &HashMap<String, Matcher>,
+    web_driver_session: &WebDriverSession,
+) -> Vec<ValidationResult> {
+    let mut results: Vec<ValidationResult> = Vec::new();
+
+    for (key, expect) in expected {
+        let label = format!("{} {}", area, key);
+        match area.get_item(key, web_driver_session).await {
+            Ok(Some(value)) => results.push(check(&label, expect, &value, web_driver_session)),
+            Ok(None) => results.push(ValidationResult {
+                validation: ValidationReultType::FAILED,
+                message: format!("Failed: {} key [{}] not found", area, key),
+            }),
+            Err(e) => results.push(ValidationResult {
+                validation: ValidationReultType::FAILED,
+                message: format!("Failed: {}", e),
+            }),
+        }
+    }
+
+    results
+}
+
+async fn validate_state(
+    state: ElementState,
+    expect: bool,
+    web_element: &WebElement,
+) -> ValidationResult {
+    let actual = match state {
+        ElementState::Displayed => web_element.is_displayed().await,
+        ElementState::Enabled => web_element.is_enabled().await,
+        ElementState::Selected => web_element.is_selected().await,
+        ElementState::Clickable => web_element.is_clickable().await,
+    };
+
+    let actual = match actual {
+        Ok(actual) => actual,
+        Err(e) => {
+            return ValidationResult {
+                validation: ValidationReultType::FAILED,
+                message: format!("Failed: {} error {}", state, e),
+            }
+        }
+    };
+
+    let not = if expect { "" } else { "not " };
+    if actual == expect {
+        return ValidationResult {
+            validation: ValidationReultType::SUCCESS,
+            message: format!("Pass: Element is {}{}", not, state),
+        };
+    }
+
+    ValidationResult {
+        validation: ValidationReultType::FAILED,
+        message: format!("Failed: Element expected to be {}{}", not, state),
+    }
+}
+
+fn validate_absent(expect: bool, absent: bool) -> ValidationResult {
+    let not = if expect { "" } else { "not " };
+    if absent == expect {
+        return ValidationResult {
+            validation: ValidationReultType::SUCCESS,
+            message: format!("Pass: Element is {}absent", not),
+        };
+    }
+
+    ValidationResult {
+        validation: ValidationReultType::FAILED,
+        message: format!("Failed: Element expected to be {}absent", not),
+    }
+}
+
 fn get_expects(task: &HashMap<String, Value>) -> TaskResult<Vec<ValidateTypes>> {
     let task_data = match task.get(TASK_TYPE) {
         Some(task_data) => task_data.as_mapping(),
@@ -329,18 +631,47 @@ fn validate_task(task_mapping: &Mapping) -> Result<Vec<ValidateTypes>, String> {
         Some(data) => data,
     };
 
-    for expect in ["text", "innerHtml", "css", "property"] {
+    for expect in [
+        "text",
+        "innerHtml",
+        "css",
+        "property",
+        "attribute",
+        "displayed",
+        "enabled",
+        "selected",
+        "clickable",
+        "absent",
+        "count",
+        "url",
+        "title",
+        "source",
+        "cookie",
+        "local_storage",
+        "session_storage",
+    ] {
         match expect {
-            "text" | "innerHtml" => {
+            "text" | "innerHtml" | "url" | "title" | "source" => {
                 if let Some(v) = validate_data_string(expect_data, expect)? {
                     to_validate.push(v);
                 }
             }
-            "css" | "property" => {
+            "css" | "property" | "attribute" | "cookie" | "local_storage"
+            | "session_storage" => {
                 if let Some(v) = validate_data_mapping(expect_data, expect)? {
                     to_validate.push(v);
                 }
             }
+            "displayed" | "enabled" | "selected" | "clickable" | "absent" => {
+                if let Some(v) = validate_data_bool(expect_data, expect)? {
+                    to_validate.push(v);
+                }
+            }
+            "count" => {
+                if let Some(v) = validate_data_count(expect_data)? {
+                    to_validate.push(v);
+                }
+            }
             _ => continue,
         }
     }
@@ -353,18 +684,26 @@ fn validate_data_string(
     key: &str,
 ) -> Result<Option<ValidateTypes>, String> {
     if let Some(task_data) = task_mapping.get(key) {
-        let value = match task_data.as_str() {
-            None => return Err(format!("{} - value is not a string", key)),
-            Some(value) => value,
+        if !task_data.is_string() && !task_data.is_mapping() {
+            return Err(format!("{} - value is not a string", key));
+        }
+
+        let value = match Matcher::new(task_data) {
+            Ok(value) => value,
+            Err(e) => return Err(format!("{} - {}", key, e)),
         };
 
-        if value.is_empty() {
+        // A plain `text: ''` means no expectation, `{ equals: '' }` is one.
+        if task_data.as_str() == Some("") {
             return Ok(None);
         }
 
         let validate = match key {
-            "text" => Some(ValidateTypes::Text(value.to_string())),
-            "innerHtml" => Some(ValidateTypes::InnerHtml(value.to_string())),
+            "text" => Some(ValidateTypes::Text(value)),
+            "innerHtml" => Some(ValidateTypes::InnerHtml(value)),
+            "url" => Some(ValidateTypes::Url(value)),
+            "title" => Some(ValidateTypes::Title(value)),
+            "source" => Some(ValidateTypes::Source(value)),
             _ => None,
         };
 
@@ -388,11 +727,15 @@ fn validate_data_mapping(
             return Ok(None);
         }
 
-        let value = to_hash(value)?;
+        let value = to_matchers(value)?;
 
         let validate = match key {
             "css" => Some(ValidateTypes::Css(value)),
             "property" => Some(ValidateTypes::Property(value)),
+            "attribute" => Some(ValidateTypes::Attribute(value)),
+            "cookie" => Some(ValidateTypes::Cookie(value)),
+            "local_storage" => Some(ValidateTypes::Storage(StorageArea::Local, value)),
+            "session_storage" => Some(ValidateTypes::Storage(StorageArea::Session, value)),
             _ => None,
         };
 
@@ -402,6 +745,63 @@ fn validate_data_mapping(
     Ok(None)
 }
 
+fn validate_data_bool(
+    task_mapping: &Mapping,
+    key: &str,
+) -> Result<Option<ValidateTypes>, String> {
+    if let Some(task_data) = task_mapping.get(key) {
+        let value = match task_data.as_bool() {
+            None => return Err(format!("{} - value is not a bool", key)),
+            Some(value) => value,
+        };
+
+        let validate = match key {
+            "displayed" => Some(ValidateTypes::State(ElementState::Displayed, value)),
+            "enabled" => Some(ValidateTypes::State(ElementState::Enabled, value)),
+            "selected" => Some(ValidateTypes::State(ElementState::Selected, value)),
+            "clickable" => Some(ValidateTypes::State(ElementState::Clickable, value)),
+            "absent" => Some(ValidateTypes::Absent(value)),
+            _ => None,
+        };
+
+        return Ok(validate);
+    }
+
+    Ok(None)
+}
+
+/// `count: 3` or a matcher, `count: { gt: 0 }`.
+fn validate_data_count(task_mapping: &Mapping) -> Result<Option<ValidateTypes>, String> {
+    let task_data = match task_mapping.get("count") {
+        Some(task_data) => task_data,
+        None => return Ok(None),
+    };
+
+    if let Some(count) = task_data.as_u64() {
+        return Ok(Some(ValidateTypes::Count(Matcher::equals(count.to_string()))));
+    }
+
+    match Matcher::new(task_data) {
+        Ok(matcher) => Ok(Some(ValidateTypes::Count(matcher))),
+        Err(e) => Err(format!("count - {}", e)),
+    }
+}
+
+fn to_matchers(task_data: &Mapping) -> Result<HashMap<String, Matcher>, String> {
+    let mut matchers: HashMap<String, Matcher> = HashMap::new();
+
+    for (key, value) in task_data {
+        let key = match key.as_str() {
+            None => return Err(format!("Key: {:?} is not a string", key)),
+            Some(k) => k.to_owned(),
+        };
+
+        matchers.insert(key, Matcher::new(value)?);
+    }
+
+    Ok(matchers)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -409,6 +809,10 @@ mod tests {
 
     use super::*;
 
+    fn equals(expected: &str) -> Matcher {
+        Matcher::new(&Value::from(expected)).unwrap()
+    }
+
     #[test]
     fn test_empty_task() {
         let validate = HashMap::new();
@@ -439,15 +843,15 @@ mod tests {
               ";
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
-        expect_vec.push(ValidateTypes::Text("Text".to_string()));
-        expect_vec.push(ValidateTypes::InnerHtml("InnerHtml".to_string()));
+        expect_vec.push(ValidateTypes::Text(equals("Text")));
+        expect_vec.push(ValidateTypes::InnerHtml(equals("InnerHtml")));
 
-        let mut css_map: HashMap<String, String> = HashMap::new();
-        css_map.insert("color".to_string(), "rgba(0, 0, 0, 0)".to_string());
-        css_map.insert("text-indent".to_string(), "-10000px".to_string());
+        let mut css_map: HashMap<String, Matcher> = HashMap::new();
+        css_map.insert("color".to_string(), equals("rgba(0, 0, 0, 0)"));
+        css_map.insert("text-indent".to_string(), equals("-10000px"));
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
 
         expect_vec.push(ValidateTypes::Css(css_map));
         expect_vec.push(ValidateTypes::Property(property_map));
@@ -457,11 +861,14 @@ mod tests {
         let expected = Ok(Validate {
             _task_types: TaskTypes::VALIDATE,
             name: "validate".to_string(),
-            element: Element {
+            element: Some(Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
-            },
+                frame: None,
+            }),
             expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
         });
         assert_eq!(expected, result)
     }
@@ -485,12 +892,12 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut css_map: HashMap<String, String> = HashMap::new();
-        css_map.insert("color".to_string(), "rgba(0, 0, 0, 0)".to_string());
-        css_map.insert("text-indent".to_string(), "-10000px".to_string());
+        let mut css_map: HashMap<String, Matcher> = HashMap::new();
+        css_map.insert("color".to_string(), equals("rgba(0, 0, 0, 0)"));
+        css_map.insert("text-indent".to_string(), equals("-10000px"));
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
 
         expect_vec.push(ValidateTypes::Css(css_map));
         expect_vec.push(ValidateTypes::Property(property_map));
@@ -500,11 +907,14 @@ mod tests {
         let expected = Ok(Validate {
             _task_types: TaskTypes::VALIDATE,
             name: "validate".to_string(),
-            element: Element {
+            element: Some(Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
-            },
+                frame: None,
+            }),
             expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
         });
         assert_eq!(expected, result)
     }
@@ -525,8 +935,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -534,11 +944,14 @@ mod tests {
         let expected = Ok(Validate {
             _task_types: TaskTypes::VALIDATE,
             name: "validate".to_string(),
-            element: Element {
+            element: Some(Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
-            },
+                frame: None,
+            }),
             expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
         });
         assert_eq!(expected, result)
     }
@@ -560,9 +973,9 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut css_map: HashMap<String, String> = HashMap::new();
-        css_map.insert("color".to_string(), "rgba(0, 0, 0, 0)".to_string());
-        css_map.insert("text-indent".to_string(), "-10000px".to_string());
+        let mut css_map: HashMap<String, Matcher> = HashMap::new();
+        css_map.insert("color".to_string(), equals("rgba(0, 0, 0, 0)"));
+        css_map.insert("text-indent".to_string(), equals("-10000px"));
 
         expect_vec.push(ValidateTypes::Css(css_map));
 
@@ -571,11 +984,14 @@ mod tests {
         let expected = Ok(Validate {
             _task_types: TaskTypes::VALIDATE,
             name: "validate".to_string(),
-            element: Element {
+            element: Some(Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
-            },
+                frame: None,
+            }),
             expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
         });
         assert_eq!(expected, result)
     }
@@ -598,9 +1014,9 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut css_map: HashMap<String, String> = HashMap::new();
-        css_map.insert("color".to_string(), "rgba(0, 0, 0, 0)".to_string());
-        css_map.insert("text-indent".to_string(), "-10000px".to_string());
+        let mut css_map: HashMap<String, Matcher> = HashMap::new();
+        css_map.insert("color".to_string(), equals("rgba(0, 0, 0, 0)"));
+        css_map.insert("text-indent".to_string(), equals("-10000px"));
 
         expect_vec.push(ValidateTypes::Css(css_map));
 
@@ -609,11 +1025,14 @@ mod tests {
         let expected = Ok(Validate {
             _task_types: TaskTypes::VALIDATE,
             name: "validate".to_string(),
-            element: Element {
+            element: Some(Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
-            },
+                frame: None,
+            }),
             expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
         });
         assert_eq!(expected, result)
     }
@@ -635,8 +1054,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -644,11 +1063,14 @@ mod tests {
         let expected = Ok(Validate {
             _task_types: TaskTypes::VALIDATE,
             name: "validate".to_string(),
-            element: Element {
+            element: Some(Element {
                 element_type: ElementType::XPATH,
                 value: "//*[@id=\"search-form\"]/fieldset/button".to_owned(),
-            },
+                frame: None,
+            }),
             expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
         });
         assert_eq!(expected, result)
     }
@@ -666,8 +1088,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -693,8 +1115,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -721,8 +1143,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -749,8 +1171,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -776,8 +1198,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -803,8 +1225,8 @@ mod tests {
 
         let mut expect_vec: Vec<ValidateTypes> = Vec::new();
 
-        let mut property_map: HashMap<String, String> = HashMap::new();
-        property_map.insert("name".to_string(), "foo".to_string());
+        let mut property_map: HashMap<String, Matcher> = HashMap::new();
+        property_map.insert("name".to_string(), equals("foo"));
         expect_vec.push(ValidateTypes::Property(property_map));
 
         let data = serde_yaml::from_str(yaml).unwrap();
@@ -816,4 +1238,325 @@ mod tests {
         });
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn test_task_expect_matchers() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          element:
+            id: 'title'
+          expect:
+            text:
+              contains: 'Rust'
+            css:
+              color:
+                not_equals: 'rgb(0, 0, 0)'
+              ";
+
+        let text: Value = serde_yaml::from_str("contains: 'Rust'").unwrap();
+        let color: Value = serde_yaml::from_str("not_equals: 'rgb(0, 0, 0)'").unwrap();
+
+        let mut css_map: HashMap<String, Matcher> = HashMap::new();
+        css_map.insert("color".to_string(), Matcher::new(&color).unwrap());
+
+        let expect_vec: Vec<ValidateTypes> = vec![
+            ValidateTypes::Text(Matcher::new(&text).unwrap()),
+            ValidateTypes::Css(css_map),
+        ];
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Ok(Validate {
+            _task_types: TaskTypes::VALIDATE,
+            name: "validate".to_string(),
+            element: Some(Element {
+                element_type: ElementType::ID,
+                value: "title".to_owned(),
+                frame: None,
+            }),
+            expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_expect_empty_matcher() {
+        let expect: Mapping = serde_yaml::from_str("text: ''").unwrap();
+        assert_eq!(Ok(None), validate_data_string(&expect, "text"));
+
+        let expect: Mapping = serde_yaml::from_str("text: { equals: '' }").unwrap();
+        let expected = Some(ValidateTypes::Text(Matcher::equals(String::new())));
+        assert_eq!(Ok(expected), validate_data_string(&expect, "text"));
+
+        let expect: Mapping = serde_yaml::from_str("title: { not_equals: '' }").unwrap();
+        let not_equals: Value = serde_yaml::from_str("not_equals: ''").unwrap();
+        let expected = Some(ValidateTypes::Title(Matcher::new(&not_equals).unwrap()));
+        assert_eq!(Ok(expected), validate_data_string(&expect, "title"));
+    }
+
+    #[test]
+    fn test_task_expect_unknown_matcher() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          element:
+            id: 'title'
+          expect:
+            text:
+              ends_with: 'Rust'
+              ";
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Err(TaskErr {
+            message: String::from("text - Unknown matcher: ends_with"),
+            task: Some(data),
+            task_type: Some(TaskTypes::VALIDATE),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_expect_state() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          element:
+            id: 'submit'
+          expect:
+            displayed: true
+            enabled: false
+            absent: false
+              ";
+
+        let expect_vec: Vec<ValidateTypes> = vec![
+            ValidateTypes::State(ElementState::Displayed, true),
+            ValidateTypes::State(ElementState::Enabled, false),
+            ValidateTypes::Absent(false),
+        ];
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Ok(Validate {
+            _task_types: TaskTypes::VALIDATE,
+            name: "validate".to_string(),
+            element: Some(Element {
+                element_type: ElementType::ID,
+                value: "submit".to_owned(),
+                frame: None,
+            }),
+            expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_expect_state_not_bool() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          element:
+            id: 'submit'
+          expect:
+            clickable: 'yes'
+              ";
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Err(TaskErr {
+            message: String::from("clickable - value is not a bool"),
+            task: Some(data),
+            task_type: Some(TaskTypes::VALIDATE),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_validate_absent() {
+        assert_eq!(ValidationReultType::SUCCESS, validate_absent(true, true).validation);
+        let result = validate_absent(true, false);
+        assert_eq!("Failed: Element expected to be absent", result.message);
+    }
+
+    #[test]
+    fn test_task_expect_attribute_count() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          element:
+            className: 'row'
+          expect:
+            attribute:
+              href: '/wiki/Rust'
+            count:
+              between: [1, 10]
+              ";
+
+        let range: Value = serde_yaml::from_str("between: [1, 10]").unwrap();
+
+        let mut attribute_map: HashMap<String, Matcher> = HashMap::new();
+        attribute_map.insert("href".to_string(), equals("/wiki/Rust"));
+
+        let expect_vec: Vec<ValidateTypes> = vec![
+            ValidateTypes::Attribute(attribute_map),
+            ValidateTypes::Count(Matcher::new(&range).unwrap()),
+        ];
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Ok(Validate {
+            _task_types: TaskTypes::VALIDATE,
+            name: "validate".to_string(),
+            element: Some(Element {
+                element_type: ElementType::CLASSNAME,
+                value: "row".to_owned(),
+                frame: None,
+            }),
+            expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_expect_count_number() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          element:
+            className: 'row'
+          expect:
+            count: 3
+              ";
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Ok(Validate {
+            _task_types: TaskTypes::VALIDATE,
+            name: "validate".to_string(),
+            element: Some(Element {
+                element_type: ElementType::CLASSNAME,
+                value: "row".to_owned(),
+                frame: None,
+            }),
+            expects: vec![ValidateTypes::Count(equals("3"))],
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_page() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          expect:
+            url:
+              contains: '/wiki/Rust'
+            title: 'Rust'
+            cookie:
+              session:
+                not_equals: ''
+            local_storage:
+              theme: 'dark'
+              ";
+
+        let url: Value = serde_yaml::from_str("contains: '/wiki/Rust'").unwrap();
+        let session: Value = serde_yaml::from_str("not_equals: ''").unwrap();
+
+        let mut cookie_map: HashMap<String, Matcher> = HashMap::new();
+        cookie_map.insert("session".to_string(), Matcher::new(&session).unwrap());
+        let mut storage_map: HashMap<String, Matcher> = HashMap::new();
+        storage_map.insert("theme".to_string(), equals("dark"));
+
+        let expect_vec: Vec<ValidateTypes> = vec![
+            ValidateTypes::Url(Matcher::new(&url).unwrap()),
+            ValidateTypes::Title(equals("Rust")),
+            ValidateTypes::Cookie(cookie_map),
+            ValidateTypes::Storage(StorageArea::Local, storage_map),
+        ];
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Ok(Validate {
+            _task_types: TaskTypes::VALIDATE,
+            name: "validate".to_string(),
+            element: None,
+            expects: expect_vec,
+            on_fail: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_page_element_expect() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          expect:
+            title: 'Rust'
+            text: 'Rust'
+              ";
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Err(TaskErr {
+            message: String::from("No element found"),
+            task: Some(data),
+            task_type: Some(TaskTypes::CLICK),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_on_fail() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          on_fail: stop
+          timeout: 3000
+          expect:
+            title: 'Rust'
+              ";
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Ok(Validate {
+            _task_types: TaskTypes::VALIDATE,
+            name: "validate".to_string(),
+            element: None,
+            expects: vec![ValidateTypes::Title(equals("Rust"))],
+            on_fail: Some(OnFail::STOP),
+            timeout: Duration::from_millis(3000),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_on_fail_invalid() {
+        let yaml = "
+        name: 'validate'
+        validate:
+          on_fail: abort
+          expect:
+            title: 'Rust'
+              ";
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = Validate::new(&data);
+        let expected = Err(TaskErr {
+            message: String::from("on_fail: [abort] expected stop or continue"),
+            task: Some(data),
+            task_type: Some(TaskTypes::VALIDATE),
+        });
+        assert_eq!(expected, result)
+    }
 }
@@ -0,0 +1,837 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::time::Instant;
+use thirtyfour::{By, WebElement};
+
+use crate::{
+    element::Element,
+    executor::{ExecuteResult, OnFail, WebDriverSession},
+    matcher::Matcher,
+};
+
+use super::{
+    failed_validations, get_on_fail, get_task, get_task_name, Task, TaskErr, TaskOk,
+    TaskResult, TaskTypes, ValidationResult, ValidationReultType,
+};
+
+const TASK_TYPE: &str = "validate_table";
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct ValidateTable {
+    _task_types: TaskTypes,
+    name: String,
+    element: Element,
+    columns: Vec<(String, Element)>,
+    /// The first expected row with `header: true`, checked against the
+    /// table header or the `columns` names.
+    header: Option<Vec<Matcher>>,
+    expected: Vec<Vec<Matcher>>,
+    ordered: bool,
+    subset: bool,
+    on_fail: Option<OnFail>,
+}
+
+#[async_trait]
+impl Task for ValidateTable {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<ValidateTable> {
+        let name = get_task_name(task)?;
+        let validate = get_task(task, TASK_TYPE)?;
+
+        match get_table(name, validate) {
+            Ok(table) => Ok(table),
+            Err(e) => Err(TaskErr {
+                message: e,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::VALIDATETABLE),
+            }),
+        }
+    }
+
+    fn element(&self) -> Option<&Element> {
+        Some(&self.element)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        let header = match &self.header {
+            Some(header) => resolve_rows(std::slice::from_ref(header), &web_driver_session)
+                .map(|mut rows| rows.pop()),
+            None => Ok(None),
+        };
+        let header = match header {
+            Ok(header) => header,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::VALIDATETABLE),
+                    },
+                ))
+            }
+        };
+
+        let expected = match resolve_rows(&self.expected, &web_driver_session) {
+            Ok(expected) => expected,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::VALIDATETABLE),
+                    },
+                ))
+            }
+        };
+
+        let table = read_table(&self.element, &self.columns, header.is_some(), &web_driver_session);
+        let (actual_header, actual) = match table.await {
+            Ok(table) => table,
+            Err(e) => {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message: e,
+                        task: None,
+                        task_type: Some(TaskTypes::VALIDATETABLE),
+                    },
+                ))
+            }
+        };
+
+        // Cells are named by their column, their header or their position.
+        let labels: Vec<String> = match (&header, self.columns.is_empty()) {
+            (Some(header), true) => header.iter().map(|cell| cell.expected.clone()).collect(),
+            _ => self.columns.iter().map(|(name, _)| name.clone()).collect(),
+        };
+
+        let mut results: Vec<ValidationResult> = Vec::new();
+        if let Some(header) = &header {
+            results.extend(compare_header(header, actual_header.as_deref(), &labels));
+        }
+        results.extend(compare(&expected, &actual, &labels, self.ordered, self.subset));
+
+        if self.on_fail.unwrap_or(web_driver_session.on_fail) == OnFail::STOP {
+            if let Some(message) = failed_validations(&results) {
+                return Err((
+                    web_driver_session,
+                    TaskErr {
+                        message,
+                        task: None,
+                        task_type: Some(TaskTypes::VALIDATETABLE),
+                    },
+                ));
+            }
+        }
+
+        let name = self.name.clone();
+        return Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::VALIDATETABLE,
+                duration: start.elapsed().as_secs(),
+                result: Some(results),
+                path: None,
+            },
+        ));
+    }
+}
+
+fn get_table(name: String, validate: &Mapping) -> Result<ValidateTable, String> {
+    let mut expected = get_expected(validate)?;
+    let header = match get_bool(validate, "header", false)? {
+        true if expected.is_empty() => return Err("header - expected has no rows".to_string()),
+        true => Some(expected.remove(0)),
+        false => None,
+    };
+
+    Ok(ValidateTable {
+        _task_types: TaskTypes::VALIDATETABLE,
+        name,
+        element: Element::new(validate)?,
+        columns: get_columns(validate)?,
+        header,
+        expected,
+        ordered: get_bool(validate, "ordered", true)?,
+        subset: get_bool(validate, "subset", false)?,
+        on_fail: get_on_fail(validate)?,
+    })
+}
+
+fn get_columns(validate: &Mapping) -> Result<Vec<(String, Element)>, String> {
+    let columns = match validate.get("columns") {
+        None => return Ok(Vec::new()),
+        Some(columns) => match columns.as_mapping() {
+            Some(columns) => columns,
+            None => return Err("columns - value is not a map".to_string()),
+        },
+    };
+
+    let mut to_columns: Vec<(String, Element)> = Vec::new();
+    for (key, locator) in columns {
+        let key = match key.as_str() {
+            Some(key) => key.to_owned(),
+            None => return Err(format!("Key: {:?} is not a string", key)),
+        };
+
+        let element = match locator.as_mapping() {
+            Some(locator) => Element::from_locator(locator)?,
+            None => return Err(format!("columns - [{}] locator is not a map", key)),
+        };
+
+        to_columns.push((key, element));
+    }
+
+    Ok(to_columns)
+}
+
+fn get_expected(validate: &Mapping) -> Result<Vec<Vec<Matcher>>, String> {
+    let rows = match (validate.get("expected"), validate.get("expected_csv")) {
+        (Some(expected), None) => get_expected_inline(expected)?,
+        (None, Some(path)) => match path.as_str() {
+            Some(path) => read_csv(path)?,
+            None => return Err("expected_csv - value is not a string".to_string()),
+        },
+        (Some(_), Some(_)) => return Err("Use either expected or expected_csv".to_string()),
+        (None, None) => return Err("expected or expected_csv is required".to_string()),
+    };
+
+    Ok(rows)
+}
+
+fn get_expected_inline(expected: &Value) -> Result<Vec<Vec<Matcher>>, String> {
+    let rows = match expected.as_sequence() {
+        Some(rows) => rows,
+        None => return Err("expected - value is not a list".to_string()),
+    };
+
+    let mut to_rows: Vec<Vec<Matcher>> = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let cells = match row.as_sequence() {
+            Some(cells) => cells,
+            None => return Err(format!("expected - row [{}] is not a list", index + 1)),
+        };
+
+        let mut to_cells: Vec<Matcher> = Vec::new();
+        for cell in cells {
+            to_cells.push(to_cell(cell)?);
+        }
+        to_rows.push(to_cells);
+    }
+
+    Ok(to_rows)
+}
+
+/// Cells are matchers, numbers are compared as written, `42`.
+fn to_cell(cell: &Value) -> Result<Matcher, String> {
+    match cell {
+        Value::Number(n) => Ok(Matcher::equals(n.to_string())),
+        _ => Matcher::new(cell),
+    }
+}
+
+fn read_csv(path: &str) -> Result<Vec<Vec<Matcher>>, String> {
+    let mut reader = match csv::ReaderBuilder::new().has_headers(false).from_path(path) {
+        Ok(reader) => reader,
+        Err(e) => return Err(format!("expected_csv - [{}] {}", path, e)),
+    };
+
+    let mut rows: Vec<Vec<Matcher>> = Vec::new();
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => return Err(format!("expected_csv - [{}] {}", path, e)),
+        };
+        rows.push(record.iter().map(|cell| Matcher::equals(cell.to_string())).collect());
+    }
+
+    Ok(rows)
+}
+
+fn get_bool(validate: &Mapping, key: &str, default: bool) -> Result<bool, String> {
+    match validate.get(key) {
+        None => Ok(default),
+        Some(value) => match value.as_bool() {
+            Some(value) => Ok(value),
+            None => Err(format!("{} - value is not a bool", key)),
+        },
+    }
+}
+
+fn resolve_rows(
+    expected: &[Vec<Matcher>],
+    web_driver_session: &WebDriverSession,
+) -> Result<Vec<Vec<Matcher>>, String> {
+    let mut rows: Vec<Vec<Matcher>> = Vec::new();
+    for row in expected {
+        let mut cells: Vec<Matcher> = Vec::new();
+        for cell in row {
+            cells.push(cell.with_expected(web_driver_session.resolve(&cell.expected)?));
+        }
+        rows.push(cells);
+    }
+    Ok(rows)
+}
+
+/// Reads the text of every cell, and of the header when `header` is set.
+/// Without `columns` the element is a `<table>` and its own rows are read,
+/// not those of nested tables. Otherwise it matches each row, the columns
+/// are found inside it and their names are the header.
+async fn read_table(
+    element: &Element,
+    columns: &[(String, Element)],
+    header: bool,
+    web_driver_session: &WebDriverSession,
+) -> Result<(Option<Vec<String>>, Vec<Vec<String>>), String> {
+    let by = Element::find_by_resolve(element, web_driver_session)?;
+    let driver = &web_driver_session.driver;
+
+    if columns.is_empty() {
+        let table = match driver.find(by).await {
+            Ok(table) => table,
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let header = match header {
+            true => match table.find_all(By::Css(":scope > thead > tr")).await {
+                Ok(rows) => match rows.first() {
+                    Some(row) => Some(read_cells(row).await?),
+                    None => None,
+                },
+                Err(e) => return Err(e.to_string()),
+            },
+            false => None,
+        };
+
+        let rows = match table.find_all(By::Css(":scope > tbody > tr")).await {
+            Ok(rows) => rows,
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let mut cells: Vec<Vec<String>> = Vec::new();
+        for row in rows {
+            cells.push(read_cells(&row).await?);
+        }
+        return Ok((header, cells));
+    }
+
+    let rows = match driver.find_all(by).await {
+        Ok(rows) => rows,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut table: Vec<Vec<String>> = Vec::new();
+    for row in rows {
+        let mut cells: Vec<String> = Vec::new();
+
+        for (name, column) in columns {
+            let by = Element::find_by_resolve(column, web_driver_session)?;
+            let text = match row.find(by).await {
+                Ok(cell) => cell.text().await,
+                Err(e) => return Err(format!("column [{}] {}", name, e)),
+            };
+            match text {
+                Ok(text) => cells.push(text),
+                Err(e) => return Err(format!("column [{}] {}", name, e)),
+            }
+        }
+
+        table.push(cells);
+    }
+
+    let header = match header {
+        true => Some(columns.iter().map(|(name, _)| name.clone()).collect()),
+        false => None,
+    };
+    Ok((header, table))
+}
+
+/// The text of the `<th>` / `<td>` cells of `row` itself.
+async fn read_cells(row: &WebElement) -> Result<Vec<String>, String> {
+    let row_cells = match row.find_all(By::Css(":scope > th, :scope > td")).await {
+        Ok(row_cells) => row_cells,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut cells: Vec<String> = Vec::new();
+    for cell in row_cells {
+        match cell.text().await {
+            Ok(text) => cells.push(text),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    Ok(cells)
+}
+
+fn compare_header(
+    expected: &[Matcher],
+    actual: Option<&[String]>,
+    labels: &[String],
+) -> Vec<ValidationResult> {
+    let diffs = match actual {
+        Some(actual) => diff(expected, actual, labels),
+        None => vec!["not found, the table has no <thead> row".to_string()],
+    };
+
+    if diffs.is_empty() {
+        return vec![ValidationResult {
+            validation: ValidationReultType::SUCCESS,
+            message: "Pass: Header found".to_string(),
+        }];
+    }
+
+    diffs
+        .iter()
+        .map(|diff| ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: format!("Failed: Header {}", diff),
+        })
+        .collect()
+}
+
+/// Every expected row is paired with a different actual row: the row at the
+/// same position for ordered tables, any free row otherwise. Rows must have
+/// the same number of cells, a row that isn't found gets a result for each
+/// cell that differs from the closest free row.
+fn compare(
+    expected: &[Vec<Matcher>],
+    actual: &[Vec<String>],
+    labels: &[String],
+    ordered: bool,
+    subset: bool,
+) -> Vec<ValidationResult> {
+    let mut results: Vec<ValidationResult> = Vec::new();
+
+    if !subset && expected.len() != actual.len() {
+        results.push(ValidationResult {
+            validation: ValidationReultType::FAILED,
+            message: format!(
+                "Failed: Table expected [{}] rows, actual: [{}]",
+                expected.len(),
+                actual.len()
+            ),
+        });
+    }
+
+    let found = match (ordered, subset) {
+        (true, false) => (0..expected.len())
+            .map(|index| Some(index).filter(|index| fits(&expected[*index], actual.get(*index))))
+            .collect(),
+        (true, true) => match_in_order(expected, actual),
+        (false, _) => match_rows(expected, actual),
+    };
+
+    let mut used = vec![false; actual.len()];
+    for index in found.iter().flatten() {
+        used[*index] = true;
+    }
+
+    for (row_index, expected_row) in expected.iter().enumerate() {
+        if let Some(index) = found[row_index] {
+            results.push(ValidationResult {
+                validation: ValidationReultType::SUCCESS,
+                message: format!("Pass: Row [{}] found at row [{}]", row_index + 1, index + 1),
+            });
+            continue;
+        }
+
+        // Ordered tables compare with the row at the same position.
+        let closest = match (ordered, subset) {
+            (true, false) => actual
+                .get(row_index)
+                .map(|row| (row_index, diff(expected_row, row, labels))),
+            _ => (0..actual.len())
+                .filter(|index| !used[*index])
+                .map(|index| (index, diff(expected_row, &actual[index], labels)))
+                .min_by_key(|(_, diffs)| diffs.len()),
+        };
+
+        let cells: Vec<String> = expected_row.iter().map(|c| c.fail_message()).collect();
+        let messages = match closest {
+            Some((index, diffs)) if diffs.is_empty() => vec![format!(
+                "Failed: Row [{}] {} found at row [{}], out of order",
+                row_index + 1,
+                cells.join(", "),
+                index + 1
+            )],
+            Some((index, diffs)) => diffs
+                .iter()
+                .map(|diff| format!("Failed: Row [{}] {}, table row [{}]", row_index + 1, diff, index + 1))
+                .collect(),
+            None => vec![format!("Failed: Row [{}] {} not found", row_index + 1, cells.join(", "))],
+        };
+        for message in messages {
+            results.push(ValidationResult {
+                validation: ValidationReultType::FAILED,
+                message,
+            });
+        }
+    }
+
+    results
+}
+
+fn fits(expected: &[Matcher], actual: Option<&Vec<String>>) -> bool {
+    actual.is_some_and(|actual| diff(expected, actual, &[]).is_empty())
+}
+
+/// What differs between the rows, one entry per cell,
+/// `column [age] expected [9], actual [8]`.
+fn diff(expected: &[Matcher], actual: &[String], labels: &[String]) -> Vec<String> {
+    let mut diffs: Vec<String> = Vec::new();
+
+    for (index, (cell, actual)) in expected.iter().zip(actual).enumerate() {
+        let label = match labels.get(index) {
+            Some(label) => label.clone(),
+            None => (index + 1).to_string(),
+        };
+        match cell.is_match(actual) {
+            Ok(true) => {}
+            Ok(false) => diffs.push(format!(
+                "column [{}] expected {}, actual [{}]",
+                label,
+                cell.fail_message(),
+                actual
+            )),
+            Err(e) => diffs.push(format!("column [{}] {}", label, e)),
+        }
+    }
+
+    if expected.len() != actual.len() {
+        diffs.push(format!("expected [{}] cells, actual [{}]", expected.len(), actual.len()));
+    }
+
+    diffs
+}
+
+/// Ordered subsets take the first fitting row after the previous match,
+/// which finds the rows whenever they are there in order.
+fn match_in_order(expected: &[Vec<Matcher>], actual: &[Vec<String>]) -> Vec<Option<usize>> {
+    let mut start = 0;
+
+    expected
+        .iter()
+        .map(|row| {
+            let found = (start..actual.len()).find(|index| fits(row, actual.get(*index)));
+            if let Some(index) = found {
+                start = index + 1;
+            }
+            found
+        })
+        .collect()
+}
+
+/// Pairs as many expected rows as possible with different actual rows. A row
+/// taken by a looser matcher is handed over when that matcher fits another
+/// one, so `[contains a]` doesn't take the only row `[contains ab]` fits.
+fn match_rows(expected: &[Vec<Matcher>], actual: &[Vec<String>]) -> Vec<Option<usize>> {
+    let fitting: Vec<Vec<usize>> = expected
+        .iter()
+        .map(|row| (0..actual.len()).filter(|index| fits(row, actual.get(*index))).collect())
+        .collect();
+
+    let mut owners: Vec<Option<usize>> = vec![None; actual.len()];
+    for row in 0..expected.len() {
+        let mut seen = vec![false; actual.len()];
+        assign(row, &fitting, &mut owners, &mut seen);
+    }
+
+    let mut found: Vec<Option<usize>> = vec![None; expected.len()];
+    for (index, owner) in owners.iter().enumerate() {
+        if let Some(row) = owner {
+            found[*row] = Some(index);
+        }
+    }
+    found
+}
+
+fn assign(
+    row: usize,
+    fitting: &[Vec<usize>],
+    owners: &mut [Option<usize>],
+    seen: &mut [bool],
+) -> bool {
+    for index in &fitting[row] {
+        if seen[*index] {
+            continue;
+        }
+        seen[*index] = true;
+
+        let free = match owners[*index] {
+            None => true,
+            Some(owner) => assign(owner, fitting, owners, seen),
+        };
+        if free {
+            owners[*index] = Some(row);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementType;
+
+    use super::*;
+
+    fn table(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    fn expected(rows: &[&[&str]]) -> Vec<Vec<Matcher>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| Matcher::equals(cell.to_string())).collect())
+            .collect()
+    }
+
+    fn compare(
+        expected: &[Vec<Matcher>],
+        actual: &[Vec<String>],
+        ordered: bool,
+        subset: bool,
+    ) -> Vec<ValidationResult> {
+        super::compare(expected, actual, &[], ordered, subset)
+    }
+
+    fn failed(results: &[ValidationResult]) -> Vec<String> {
+        results
+            .iter()
+            .filter(|r| r.validation == ValidationReultType::FAILED)
+            .map(|r| r.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let validate = HashMap::new();
+        let result = ValidateTable::new(&validate);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(validate),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'users'
+        validate_table:
+          element:
+            className: 'user-row'
+          columns:
+            name:
+              className: 'name'
+            age:
+              xPath: './/td[2]'
+          header: true
+          ordered: false
+          expected:
+            - ['name', 'age']
+            - ['Ferris', 8]
+            - [{ starts_with: 'Cor' }, 3]
+              ";
+
+        let corro: Value = serde_yaml::from_str("starts_with: 'Cor'").unwrap();
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = ValidateTable::new(&data);
+        let expected = Ok(ValidateTable {
+            _task_types: TaskTypes::VALIDATETABLE,
+            name: "users".to_string(),
+            element: Element {
+                element_type: ElementType::CLASSNAME,
+                value: "user-row".to_owned(),
+                frame: None,
+            },
+            columns: vec![
+                (
+                    "name".to_string(),
+                    Element {
+                        element_type: ElementType::CLASSNAME,
+                        value: "name".to_owned(),
+                        frame: None,
+                    },
+                ),
+                (
+                    "age".to_string(),
+                    Element {
+                        element_type: ElementType::XPATH,
+                        value: ".//td[2]".to_owned(),
+                        frame: None,
+                    },
+                ),
+            ],
+            header: Some(vec![
+                Matcher::equals("name".to_string()),
+                Matcher::equals("age".to_string()),
+            ]),
+            expected: vec![
+                vec![Matcher::equals("Ferris".to_string()), Matcher::equals("8".to_string())],
+                vec![Matcher::new(&corro).unwrap(), Matcher::equals("3".to_string())],
+            ],
+            ordered: false,
+            subset: false,
+            on_fail: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_missing_expected() {
+        let yaml = "
+        name: 'users'
+        validate_table:
+          element:
+            id: 'users'
+              ";
+
+        let data = serde_yaml::from_str(yaml).unwrap();
+        let result = ValidateTable::new(&data);
+        let expected = Err(TaskErr {
+            message: String::from("expected or expected_csv is required"),
+            task: Some(data),
+            task_type: Some(TaskTypes::VALIDATETABLE),
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_csv() {
+        let path = std::env::temp_dir().join(format!("ls-validate-table-{}.csv", std::process::id()));
+        std::fs::write(&path, "name,age\n\"Ferris, the crab\",8\n").unwrap();
+
+        let result = read_csv(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let expected = Ok(expected(&[&["name", "age"], &["Ferris, the crab", "8"]]));
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_compare_ordered() {
+        let actual = table(&[&["Ferris", "8"], &["Corro", "3"]]);
+
+        let results = compare(&expected(&[&["Ferris", "8"], &["Corro", "3"]]), &actual, true, false);
+        assert_eq!(2, results.len());
+        assert!(failed(&results).is_empty());
+
+        let results = compare(&expected(&[&["Ferris", "9"]]), &actual, true, false);
+        assert_eq!(
+            vec![
+                "Failed: Table expected [1] rows, actual: [2]".to_string(),
+                "Failed: Row [1] column [2] expected [9], actual [8], table row [1]".to_string(),
+            ],
+            failed(&results)
+        );
+    }
+
+    #[test]
+    fn test_compare_extra_cells() {
+        let actual = table(&[&["Ferris", "8", "crab"]]);
+
+        let expected_message =
+            "Failed: Row [1] expected [2] cells, actual [3], table row [1]".to_string();
+        for (ordered, subset) in [(true, false), (false, false), (true, true), (false, true)] {
+            let results = compare(&expected(&[&["Ferris", "8"]]), &actual, ordered, subset);
+            assert_eq!(vec![expected_message.clone()], failed(&results));
+        }
+    }
+
+    #[test]
+    fn test_compare_unordered() {
+        let actual = table(&[&["Ferris", "8"], &["Corro", "3"]]);
+
+        let results = compare(&expected(&[&["Corro", "3"], &["Ferris", "8"]]), &actual, false, false);
+        assert!(failed(&results).is_empty());
+
+        let results = compare(&expected(&[&["Corro", "3"], &["Corro", "3"]]), &actual, false, false);
+        assert_eq!(
+            vec![
+                "Failed: Row [2] column [1] expected [Corro], actual [Ferris], table row [1]".to_string(),
+                "Failed: Row [2] column [2] expected [3], actual [8], table row [1]".to_string(),
+            ],
+            failed(&results)
+        );
+    }
+
+    #[test]
+    fn test_compare_header() {
+        let labels = vec!["name".to_string(), "age".to_string()];
+        let header = vec![Matcher::equals("name".to_string()), Matcher::equals("age".to_string())];
+
+        let actual = vec!["name".to_string(), "age".to_string()];
+        assert!(failed(&compare_header(&header, Some(&actual), &labels)).is_empty());
+
+        let actual = vec!["name".to_string(), "years".to_string()];
+        assert_eq!(
+            vec!["Failed: Header column [age] expected [age], actual [years]".to_string()],
+            failed(&compare_header(&header, Some(&actual), &labels))
+        );
+
+        assert_eq!(
+            vec!["Failed: Header not found, the table has no <thead> row".to_string()],
+            failed(&compare_header(&header, None, &labels))
+        );
+    }
+
+    #[test]
+    fn test_compare_column_labels() {
+        let labels = vec!["name".to_string(), "age".to_string()];
+        let results = super::compare(
+            &expected(&[&["Ferris", "9"]]),
+            &table(&[&["Ferris", "8"]]),
+            &labels,
+            true,
+            false,
+        );
+        assert_eq!(
+            vec!["Failed: Row [1] column [age] expected [9], actual [8], table row [1]".to_string()],
+            failed(&results)
+        );
+    }
+
+    #[test]
+    fn test_compare_unordered_overlapping() {
+        let contains = |text: &str| {
+            let value: Value = serde_yaml::from_str(&format!("contains: '{}'", text)).unwrap();
+            Matcher::new(&value).unwrap()
+        };
+        let expected = vec![vec![contains("a")], vec![contains("ab")]];
+        let actual = table(&[&["ab"], &["a"]]);
+
+        assert!(failed(&compare(&expected, &actual, false, false)).is_empty());
+        assert!(failed(&compare(&expected, &actual, false, true)).is_empty());
+    }
+
+    #[test]
+    fn test_compare_subset() {
+        let actual = table(&[&["Ferris", "8"], &["Corro", "3"], &["Tux", "30"]]);
+
+        let results = compare(&expected(&[&["Ferris", "8"], &["Tux", "30"]]), &actual, true, true);
+        assert!(failed(&results).is_empty());
+
+        let results = compare(&expected(&[&["Tux", "30"], &["Ferris", "8"]]), &actual, true, true);
+        assert_eq!(
+            vec!["Failed: Row [2] [Ferris], [8] found at row [1], out of order".to_string()],
+            failed(&results)
+        );
+
+        let results = compare(&expected(&[&["Tux", "30"], &["Ferris", "8"]]), &actual, false, true);
+        assert!(failed(&results).is_empty());
+    }
+}
@@ -31,6 +31,10 @@ impl Task for Wait {
         })
     }
 
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     async fn execute(&self, web_driver_session: WebDriverSession) -> ExecuteResult {
         let start = Instant::now();
 
@@ -43,7 +47,8 @@ impl Task for Wait {
                 name,
                 task_type: TaskTypes::WAIT,
                 duration: start.elapsed().as_secs(),
-                result: None
+                result: None,
+                path: None
             },
         ));
     }
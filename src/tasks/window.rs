@@ -0,0 +1,413 @@
+use async_trait::async_trait;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::time::Instant;
+use thirtyfour::WindowHandle;
+
+use super::{get_task, get_task_name, Task, TaskErr, TaskOk, TaskResult, TaskTypes};
+use crate::{
+    executor::{ExecuteResult, WebDriverSession},
+    matcher::Matcher,
+    variables::Variable,
+};
+
+const TASK_TYPE: &str = "window";
+
+/// Which window `switch` moves to.
+#[derive(Debug, PartialEq, Eq)]
+enum SwitchTo {
+    /// Negative indexes count from the last window, `-1` is the newest.
+    Index(i64),
+    Handle(String),
+    Title(Matcher),
+    Url(Matcher),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum WindowAction {
+    NewTab,
+    NewWindow,
+    Switch(SwitchTo),
+    Handles(String),
+    Close,
+    SetSize(u32, u32),
+    Maximize,
+    Minimize,
+}
+
+/// Works on the browser windows and tabs, `close` closes the current window
+/// only while the `close` task quits the whole session.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Window {
+    _task_types: TaskTypes,
+    name: String,
+    action: WindowAction,
+}
+
+#[async_trait]
+impl Task for Window {
+    fn new(task: &HashMap<String, Value>) -> TaskResult<Window> {
+        let name = get_task_name(task)?;
+        let window = get_task(task, TASK_TYPE)?;
+
+        match get_action(window) {
+            Ok(action) => Ok(Window {
+                _task_types: TaskTypes::WINDOW,
+                name,
+                action,
+            }),
+            Err(message) => Err(TaskErr {
+                message,
+                task: Some(task.clone()),
+                task_type: Some(TaskTypes::WINDOW),
+            }),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(&self, mut web_driver_session: WebDriverSession) -> ExecuteResult {
+        let start = Instant::now();
+
+        if let Err(e) = self.perform(&mut web_driver_session).await {
+            return Err((
+                web_driver_session,
+                TaskErr {
+                    message: e,
+                    task: None,
+                    task_type: Some(TaskTypes::WINDOW),
+                },
+            ));
+        }
+
+        let name = self.name.clone();
+        Ok((
+            web_driver_session,
+            TaskOk {
+                name,
+                task_type: TaskTypes::WINDOW,
+                duration: start.elapsed().as_secs(),
+                result: None,
+                path: None,
+            },
+        ))
+    }
+}
+
+impl Window {
+    async fn perform(&self, web_driver_session: &mut WebDriverSession) -> Result<(), String> {
+        let driver = &web_driver_session.driver;
+
+        let result = match &self.action {
+            // A new tab or window is only useful once switched to.
+            WindowAction::NewTab => match driver.new_tab().await {
+                Ok(handle) => driver.switch_to_window(handle).await,
+                Err(e) => Err(e),
+            },
+            WindowAction::NewWindow => match driver.new_window().await {
+                Ok(handle) => driver.switch_to_window(handle).await,
+                Err(e) => Err(e),
+            },
+            WindowAction::Switch(switch_to) => {
+                return self.switch(switch_to, web_driver_session).await
+            }
+            WindowAction::Handles(save_as) => {
+                let handles = windows(web_driver_session).await?;
+                let handles = handles
+                    .into_iter()
+                    .map(|handle| Variable::String(handle.into()))
+                    .collect();
+                web_driver_session.add_variable(save_as, Variable::List(handles))?;
+                return Ok(());
+            }
+            // Commands fail without a current window, so the first one left
+            // takes over.
+            WindowAction::Close => match driver.close_window().await {
+                Ok(_) => match windows(web_driver_session).await?.into_iter().next() {
+                    Some(handle) => driver.switch_to_window(handle).await,
+                    None => Ok(()),
+                },
+                Err(e) => Err(e),
+            },
+            WindowAction::SetSize(width, height) => match driver.get_window_rect().await {
+                Ok(rect) => {
+                    let (x, y) = (rect.x.max(0) as u32, rect.y.max(0) as u32);
+                    driver.set_window_rect(x, y, *width, *height).await
+                }
+                Err(e) => Err(e),
+            },
+            WindowAction::Maximize => driver.maximize_window().await,
+            WindowAction::Minimize => driver.minimize_window().await,
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Window error: {}", e)),
+        }
+    }
+
+    async fn switch(
+        &self,
+        switch_to: &SwitchTo,
+        web_driver_session: &WebDriverSession,
+    ) -> Result<(), String> {
+        let driver = &web_driver_session.driver;
+        let handles = windows(web_driver_session).await?;
+
+        let (label, matcher) = match switch_to {
+            SwitchTo::Index(index) => {
+                let position = match *index < 0 {
+                    true => handles.len() as i64 + index,
+                    false => *index,
+                };
+                let handle = match usize::try_from(position).ok().and_then(|i| handles.get(i)) {
+                    Some(handle) => handle.clone(),
+                    None => {
+                        return Err(format!(
+                            "No window at index [{}], {} window(s) open",
+                            index,
+                            handles.len()
+                        ))
+                    }
+                };
+                return switch_to_window(handle, web_driver_session).await;
+            }
+            SwitchTo::Handle(handle) => {
+                let handle = web_driver_session.resolve(handle)?;
+                return match WindowHandle::try_from(handle.as_str()) {
+                    Ok(handle) => switch_to_window(handle, web_driver_session).await,
+                    Err(_) => Err(format!("Invalid window handle [{}]", handle)),
+                };
+            }
+            SwitchTo::Title(matcher) => ("title", matcher),
+            SwitchTo::Url(matcher) => ("url", matcher),
+        };
+
+        let expected = web_driver_session.resolve(&matcher.expected)?;
+        let matcher = matcher.with_expected(expected);
+
+        let current = driver.window().await.map_err(|e| e.to_string())?;
+        for handle in handles {
+            switch_to_window(handle, web_driver_session).await?;
+            let actual = match label {
+                "title" => driver.title().await.map_err(|e| e.to_string())?,
+                _ => driver
+                    .current_url()
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .to_string(),
+            };
+
+            if matcher.is_match(&actual)? {
+                return Ok(());
+            }
+        }
+
+        // Back where the task started, the next tasks should not run in a
+        // random window.
+        switch_to_window(current, web_driver_session).await?;
+        Err(format!(
+            "No window with {} {}",
+            label,
+            matcher.fail_message()
+        ))
+    }
+}
+
+async fn windows(web_driver_session: &WebDriverSession) -> Result<Vec<WindowHandle>, String> {
+    match web_driver_session.driver.windows().await {
+        Ok(handles) => Ok(handles),
+        Err(e) => Err(format!("Unable to list windows: {}", e)),
+    }
+}
+
+async fn switch_to_window(
+    handle: WindowHandle,
+    web_driver_session: &WebDriverSession,
+) -> Result<(), String> {
+    match web_driver_session.driver.switch_to_window(handle).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Unable to switch window: {}", e)),
+    }
+}
+
+fn get_action(window: &Mapping) -> Result<WindowAction, String> {
+    let action = match window.get("action").map(|action| action.as_str()) {
+        Some(Some(action)) => action,
+        Some(None) => return Err("action field is not a string".to_string()),
+        None => return Err("action field not found".to_string()),
+    };
+
+    match action {
+        "new_tab" => Ok(WindowAction::NewTab),
+        "new_window" => Ok(WindowAction::NewWindow),
+        "switch" => Ok(WindowAction::Switch(get_switch_to(window)?)),
+        "handles" => match window.get("save_as").and_then(|save_as| save_as.as_str()) {
+            Some(save_as) => Ok(WindowAction::Handles(save_as.to_string())),
+            None => Err("handles - save_as field is not a string".to_string()),
+        },
+        "close" => Ok(WindowAction::Close),
+        "set_size" => Ok(WindowAction::SetSize(
+            get_size(window, "width")?,
+            get_size(window, "height")?,
+        )),
+        "maximize" => Ok(WindowAction::Maximize),
+        "minimize" => Ok(WindowAction::Minimize),
+        _ => Err(format!("Unknown window action: {}", action)),
+    }
+}
+
+fn get_switch_to(window: &Mapping) -> Result<SwitchTo, String> {
+    let keys: Vec<&str> = ["index", "handle", "title", "url"]
+        .into_iter()
+        .filter(|key| window.contains_key(*key))
+        .collect();
+
+    let key = match keys.as_slice() {
+        [key] => *key,
+        [] => return Err("switch - expected one of index, handle, title or url".to_string()),
+        _ => return Err("switch - only one of index, handle, title or url is allowed".to_string()),
+    };
+
+    let value = &window[key];
+    match key {
+        "index" => match value.as_i64() {
+            Some(index) => Ok(SwitchTo::Index(index)),
+            None => Err("index field is not a number".to_string()),
+        },
+        "handle" => match value.as_str() {
+            Some(handle) => Ok(SwitchTo::Handle(handle.to_string())),
+            None => Err("handle field is not a string".to_string()),
+        },
+        "title" => Ok(SwitchTo::Title(Matcher::new(value)?)),
+        _ => Ok(SwitchTo::Url(Matcher::new(value)?)),
+    }
+}
+
+fn get_size(window: &Mapping, key: &str) -> Result<u32, String> {
+    match window.get(key).map(|size| size.as_u64()) {
+        Some(Some(size)) if size > 0 && size <= u32::MAX as u64 => Ok(size as u32),
+        Some(_) => Err(format!("{} field is not a positive number", key)),
+        None => Err(format!("set_size - {} field not found", key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(yaml: &str) -> Result<WindowAction, String> {
+        let task = serde_yaml::from_str(yaml).unwrap();
+        Window::new(&task)
+            .map(|window| window.action)
+            .map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_empty_task() {
+        let task = HashMap::new();
+        let result = Window::new(&task);
+        let expected = Err(TaskErr {
+            message: String::from("Malformed Task"),
+            task: Some(task),
+            task_type: None,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task() {
+        let yaml = "
+        name: 'open the docs'
+        window:
+            action: new_tab
+              ";
+
+        let task = serde_yaml::from_str(yaml).unwrap();
+        let result = Window::new(&task);
+        let expected = Ok(Window {
+            _task_types: TaskTypes::WINDOW,
+            name: "open the docs".to_owned(),
+            action: WindowAction::NewTab,
+        });
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_task_switch() {
+        let yaml = "
+        name: 'go to the popup'
+        window:
+            action: switch
+            index: -1
+              ";
+        assert_eq!(Ok(WindowAction::Switch(SwitchTo::Index(-1))), action(yaml));
+
+        let yaml = "
+        name: 'go to the login'
+        window:
+            action: switch
+            url:
+                contains: 'oauth'
+              ";
+        let url: Value = serde_yaml::from_str("contains: 'oauth'").unwrap();
+        let expected = Ok(WindowAction::Switch(SwitchTo::Url(Matcher::new(&url).unwrap())));
+        assert_eq!(expected, action(yaml));
+
+        let yaml = "
+        name: 'go to the login'
+        window:
+            action: switch
+            title: 'Sign in'
+            index: 1
+              ";
+        let expected =
+            Err("switch - only one of index, handle, title or url is allowed".to_string());
+        assert_eq!(expected, action(yaml));
+    }
+
+    #[test]
+    fn test_task_handles_and_size() {
+        let yaml = "
+        name: 'list the windows'
+        window:
+            action: handles
+            save_as: 'windows'
+              ";
+        assert_eq!(
+            Ok(WindowAction::Handles("windows".to_owned())),
+            action(yaml)
+        );
+
+        let yaml = "
+        name: 'mobile size'
+        window:
+            action: set_size
+            width: 375
+            height: 812
+              ";
+        assert_eq!(Ok(WindowAction::SetSize(375, 812)), action(yaml));
+
+        let yaml = "
+        name: 'mobile size'
+        window:
+            action: set_size
+            width: 375
+              ";
+        let expected = Err("set_size - height field not found".to_string());
+        assert_eq!(expected, action(yaml));
+    }
+
+    #[test]
+    fn test_unknown_action() {
+        let yaml = "
+        name: 'resize'
+        window:
+            action: fullscreen
+              ";
+        let expected = Err("Unknown window action: fullscreen".to_string());
+        assert_eq!(expected, action(yaml));
+    }
+}
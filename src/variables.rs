@@ -1,31 +1,310 @@
-use lazy_static::lazy_static;
+mod filter;
+mod variable;
+
+use serde_yaml::Mapping;
 use std::collections::HashMap;
 
+pub use self::filter::Filter;
+pub use self::variable::Variable;
+
+/// Parsed piece of a template string.
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Variable(Token),
+}
+
+/// A `{name}` / `{name | filter}` / `{name ?? default}` reference found in
+/// a template.
+#[derive(Debug, PartialEq, Eq)]
+struct Token {
+    raw: String,
+    path: Vec<String>,
+    default: Option<Vec<Segment>>,
+    /// The error of an unknown filter, reported once the token resolves.
+    filters: Result<Vec<Filter>, String>,
+}
+
+/// Replaces every `{name}` in `text` with its value.
+///
+/// Unknown variables without a default are left untouched.
+pub fn resolve_variables(text: &str, vars: &HashMap<String, Variable>) -> String {
+    let segments = parse(text);
+    match render(&segments, vars, false) {
+        Ok(resolved) => resolved,
+        Err(_) => String::from(text),
+    }
+}
+
+/// Same as [`resolve_variables`] but fails on the first unknown variable
+/// that has no default.
+pub fn resolve_variables_strict(
+    text: &str,
+    vars: &HashMap<String, Variable>,
+) -> Result<String, String> {
+    let segments = parse(text);
+    render(&segments, vars, true)
+}
+
+/// Whether `text` refers to any variable, `{{` alone doesn't.
+pub fn has_variables(text: &str) -> bool {
+    parse(text)
+        .iter()
+        .any(|segment| matches!(segment, Segment::Variable(_)))
+}
+
+/// Converts a `set_vars` mapping into typed variables, in the order they
+/// are written.
+pub fn to_variables(mapping: &Mapping) -> Result<Vec<(String, Variable)>, String> {
+    let mut variables: Vec<(String, Variable)> = Vec::new();
+
+    for (key, value) in mapping {
+        let key = match key.as_str() {
+            None => return Err(format!("Key: {:?} is not a string", key)),
+            Some(k) => k.to_owned(),
+        };
+
+        variables.push((key, Variable::try_from(value)?));
+    }
+
+    Ok(variables)
+}
+
+/// Looks up a variable by its dotted path. A key containing the dots
+/// (`user.email`) wins over walking into a `user` map.
+pub fn get_variable<'a>(path: &[String], vars: &'a HashMap<String, Variable>) -> Option<&'a Variable> {
+    if let Some(variable) = vars.get(&path.join(".")) {
+        return Some(variable);
+    }
+
+    let (first, rest) = path.split_first()?;
+    vars.get(first)?.get_path(rest)
+}
+
+/// Resolves a single `{name}` template keeping the variable type. Anything
+/// else (defaults, filters, text around it) resolves to a string.
+pub fn resolve_variable(text: &str, vars: &HashMap<String, Variable>) -> Result<Variable, String> {
+    let segments = parse(text);
+
+    if let [Segment::Variable(token)] = segments.as_slice() {
+        if token.filters == Ok(Vec::new()) {
+            if let Some(variable) = get_variable(&token.path, vars) {
+                return Ok(variable.clone());
+            }
+        }
+    }
+
+    Ok(Variable::String(render(&segments, vars, true)?))
+}
+
+fn render(
+    segments: &[Segment],
+    vars: &HashMap<String, Variable>,
+    strict: bool,
+) -> Result<String, String> {
+    let mut resolved_text = String::new();
+
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => resolved_text.push_str(text),
+            Segment::Variable(token) => {
+                let filters = match &token.filters {
+                    Ok(filters) => filters,
+                    Err(e) if strict => return Err(e.clone()),
+                    Err(_) => {
+                        resolved_text.push_str(&token.raw);
+                        continue;
+                    }
+                };
+
+                let value = match (get_variable(&token.path, vars), &token.default) {
+                    (Some(value), _) => value.to_string(),
+                    (None, Some(default)) => render(default, vars, strict)?,
+                    (None, None) if strict => {
+                        return Err(format!("Undefined variable: {}", token.path.join(".")))
+                    }
+                    (None, None) => {
+                        resolved_text.push_str(&token.raw);
+                        continue;
+                    }
+                };
+
+                match apply_filters(filters, value) {
+                    Ok(value) => resolved_text.push_str(&value),
+                    Err(e) if strict => return Err(e),
+                    Err(_) => resolved_text.push_str(&token.raw),
+                }
+            }
+        }
+    }
+
+    Ok(resolved_text)
+}
+
+fn apply_filters(filters: &[Filter], value: String) -> Result<String, String> {
+    let mut value = value;
+    for filter in filters {
+        value = filter.apply(&value)?;
+    }
+    Ok(value)
+}
+
+fn parse(text: &str) -> Vec<Segment> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut pos = 0;
+    parse_segments(&chars, &mut pos, false)
+}
+
+/// Parses literal text and variables until the end of input, or until the
+/// closing `}` of the enclosing token when `nested` is set.
+fn parse_segments(chars: &[char], pos: &mut usize, nested: bool) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut literal = String::new();
+
+    while *pos < chars.len() {
+        let c = chars[*pos];
+
+        if c == '{' && chars.get(*pos + 1) == Some(&'{') {
+            literal.push('{');
+            *pos += 2;
+            continue;
+        }
+
+        if c == '{' {
+            if let Some(token) = parse_token(chars, pos) {
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Variable(token));
+                continue;
+            }
+        }
+
+        if nested && c == '}' {
+            break;
+        }
+
+        if nested && c == '\\' && *pos + 1 < chars.len() {
+            literal.push(chars[*pos + 1]);
+            *pos += 2;
+            continue;
+        }
 
-use regex::Regex;
+        literal.push(c);
+        *pos += 1;
+    }
 
-pub fn resolve_variables(text: &str, vars: &HashMap<String, String>) -> String {
-    lazy_static! {
-        static ref RE_STRING: Regex = Regex::new(r"\{([[:alpha:]_]*)\}").unwrap();
-        static ref RE_DEFAULT: Regex = Regex::new(r"\{([[:alpha:]_]*)\|(.*)\}").unwrap();
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
     }
 
-    let mut resolved_text: String = String::from(text);
-    for cap in RE_STRING.captures_iter(text) {
-        if let Some(value) = vars.get(&cap[1]) {
-            resolved_text = resolved_text.replace(&cap[0], value);
+    segments
+}
+
+/// Tries to parse a token starting at the `{` under `pos`. On success `pos`
+/// is moved past the closing `}`, otherwise it is left untouched.
+fn parse_token(chars: &[char], pos: &mut usize) -> Option<Token> {
+    let start = *pos;
+    let mut cursor = start + 1;
+
+    skip_whitespace(chars, &mut cursor);
+    let name_start = cursor;
+    while cursor < chars.len() && is_name_char(chars[cursor]) {
+        cursor += 1;
+    }
+    let name: String = chars[name_start..cursor].iter().collect();
+    let path: Vec<String> = name.split('.').map(String::from).collect();
+    // Names start like identifiers, so regex counts like `\d{3}` stay text.
+    if path.iter().any(|part| part.is_empty()) || !is_name_start(chars[name_start]) {
+        return None;
+    }
+    skip_whitespace(chars, &mut cursor);
+
+    let mut filters: Result<Vec<Filter>, String> = Ok(Vec::new());
+    while chars.get(cursor) == Some(&'|') {
+        cursor += 1;
+        let filter = parse_filter(chars, &mut cursor);
+        // Not a filter name, like the JavaScript `{a || b}`.
+        if !filter.trim_start().starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        if let Ok(parsed) = &mut filters {
+            match Filter::parse(&filter) {
+                Ok(filter) => parsed.push(filter),
+                Err(e) => filters = Err(e),
+            }
         }
     }
 
-    for cap in RE_DEFAULT.captures_iter(text) {
-        if let Some(value) = vars.get(&cap[1]) {
-            resolved_text = resolved_text.replace(&cap[0], value);
-        } else {
-            resolved_text = resolved_text.replace(&cap[0], &cap[2]);
+    // Everything up to the closing `}` is the default, `|` included.
+    let default = match (chars.get(cursor), chars.get(cursor + 1)) {
+        (Some('?'), Some('?')) => {
+            cursor += 2;
+            Some(trim_segments(parse_segments(chars, &mut cursor, true)))
         }
+        _ => None,
+    };
+
+    if chars.get(cursor) != Some(&'}') {
+        return None;
     }
 
-    resolved_text
+    cursor += 1;
+    *pos = cursor;
+
+    Some(Token {
+        raw: chars[start..cursor].iter().collect(),
+        path,
+        default,
+        filters,
+    })
+}
+
+/// Reads a filter up to the next `|`, `??` or `}`, `\` escapes them.
+fn parse_filter(chars: &[char], pos: &mut usize) -> String {
+    let mut filter = String::new();
+
+    while let Some(&c) = chars.get(*pos) {
+        match c {
+            '|' | '}' => break,
+            '?' if chars.get(*pos + 1) == Some(&'?') => break,
+            '\\' if *pos + 1 < chars.len() => {
+                filter.push(chars[*pos + 1]);
+                *pos += 2;
+            }
+            _ => {
+                filter.push(c);
+                *pos += 1;
+            }
+        }
+    }
+
+    filter
+}
+
+/// Drops the spaces around `??` so `{name ?? Foo}` defaults to `Foo`.
+fn trim_segments(mut segments: Vec<Segment>) -> Vec<Segment> {
+    if let Some(Segment::Literal(text)) = segments.first_mut() {
+        *text = text.trim_start().to_string();
+    }
+    if let Some(Segment::Literal(text)) = segments.last_mut() {
+        *text = text.trim_end().to_string();
+    }
+    segments
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+fn skip_whitespace(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && chars[*pos] == ' ' {
+        *pos += 1;
+    }
 }
 
 #[cfg(test)]
@@ -36,9 +315,9 @@ mod tests {
     fn test_resolve_variables() {
         let text: &str = "Hi, {name} {surname} \nHow are you {name}";
 
-        let mut vars: HashMap<String, String> = HashMap::new();
-        vars.insert("name".to_string(), "Foo".to_string());
-        vars.insert("surname".to_string(), "Bar".to_string());
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("name".to_string(), Variable::from("Foo"));
+        vars.insert("surname".to_string(), Variable::from("Bar"));
 
         let result = resolve_variables(text, &vars);
         let expected = "Hi, Foo Bar \nHow are you Foo".to_string();
@@ -59,8 +338,8 @@ mod tests {
     fn test_resolve_variables_missing_var() {
         let text: &str = "Hi, {name} {surname} \nHow are you {name}";
 
-        let mut vars: HashMap<String, String> = HashMap::new();
-        vars.insert("name".to_string(), "Foo".to_string());
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("name".to_string(), Variable::from("Foo"));
 
         let result = resolve_variables(text, &vars);
         let expected = "Hi, Foo {surname} \nHow are you Foo".to_string();
@@ -73,8 +352,8 @@ mod tests {
     fn test_resolve_variables_with_underscore() {
         let text: &str = "Hi, {first_name}";
 
-        let mut vars: HashMap<String, String> = HashMap::new();
-        vars.insert("first_name".to_string(), "Foo".to_string());
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("first_name".to_string(), Variable::from("Foo"));
 
         let result = resolve_variables(text, &vars);
         let expected = "Hi, Foo".to_string();
@@ -84,9 +363,9 @@ mod tests {
 
     #[test]
     fn test_resolve_variables_with_default() {
-        let text: &str = "Hi, {first_name|Foo}";
+        let text: &str = "Hi, {first_name ?? Foo}";
 
-        let vars: HashMap<String, String> = HashMap::new();
+        let vars: HashMap<String, Variable> = HashMap::new();
 
         let result = resolve_variables(text, &vars);
         let expected = "Hi, Foo".to_string();
@@ -96,15 +375,207 @@ mod tests {
 
     #[test]
     fn test_resolve_variables_with_multiple_default() {
-        let text: &str = "Hi, {first_name|Foo} {surname}\nHow are you {first_name|Foo}\nNo default {first_name}\n{age}";
+        let text: &str = "Hi, {first_name??Foo} {surname}\nHow are you {first_name ?? Foo}\nNo default {first_name}\n{age}";
 
-        let mut vars: HashMap<String, String> = HashMap::new();
-        vars.insert("surname".to_string(), "Bar".to_string());
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("surname".to_string(), Variable::from("Bar"));
 
         let result = resolve_variables(text, &vars);
         let expected = "Hi, Foo Bar\nHow are you Foo\nNo default {first_name}\n{age}".to_string();
 
         assert_eq!(expected, result)
     }
-    
+
+    #[test]
+    fn test_resolve_variables_with_digits_and_dots() {
+        let text: &str = "{user1} <{user.email}>";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("user1".to_string(), Variable::from("Foo"));
+        vars.insert("user.email".to_string(), Variable::from("foo@bar.com"));
+
+        let result = resolve_variables(text, &vars);
+        let expected = "Foo <foo@bar.com>".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_escaped() {
+        let text: &str = "{{name} is {name}";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("name".to_string(), Variable::from("Foo"));
+
+        let result = resolve_variables(text, &vars);
+        let expected = "{name} is Foo".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_default_with_brace() {
+        let text: &str = "{a ?? x\\}y} {b??z}";
+
+        let result = resolve_variables(text, &HashMap::new());
+        let expected = "x}y z".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_nested_default() {
+        let text: &str = "Hi, {nickname ?? {first_name ?? Foo}}";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        let result = resolve_variables(text, &vars);
+        assert_eq!("Hi, Foo", result);
+
+        vars.insert("first_name".to_string(), Variable::from("Bar"));
+        let result = resolve_variables(text, &vars);
+        assert_eq!("Hi, Bar", result);
+    }
+
+    #[test]
+    fn test_resolve_variables_not_a_variable() {
+        let text: &str = "function() { return 1; }";
+
+        let result = resolve_variables(text, &HashMap::new());
+
+        assert_eq!(text, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_strict() {
+        let text: &str = "Hi, {name} {surname ?? Bar}";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("name".to_string(), Variable::from("Foo"));
+        assert_eq!(Ok("Hi, Foo Bar".to_string()), resolve_variables_strict(text, &vars));
+
+        let result = resolve_variables_strict(text, &HashMap::new());
+        assert_eq!(Err("Undefined variable: name".to_string()), result)
+    }
+
+    #[test]
+    fn test_resolve_variables_filters() {
+        let text: &str = "{name | upper} {price | trim | number} {query|urlencode}";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("name".to_string(), Variable::from("Foo"));
+        vars.insert("price".to_string(), Variable::from(" $1,299.00 "));
+        vars.insert("query".to_string(), Variable::from("rust lang"));
+
+        let result = resolve_variables(text, &vars);
+        let expected = "FOO 1299.00 rust%20lang".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_filter_with_args() {
+        let text: &str = "{text | replace:-:/} {date | format:%d/%m}";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("text".to_string(), Variable::from("a-b-c"));
+        vars.insert("date".to_string(), Variable::from("2023-12-24"));
+
+        let result = resolve_variables(text, &vars);
+        let expected = "a/b/c 24/12".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_default_and_filter() {
+        let text: &str = "Hi, {name | upper ?? foo}";
+
+        let result = resolve_variables(text, &HashMap::new());
+        let expected = "Hi, FOO".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_default_with_pipe() {
+        let text: &str = "{status ?? trim} {a ?? x|y} {b??c??d}";
+
+        let result = resolve_variables(text, &HashMap::new());
+        let expected = "trim x|y c??d".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variables_unknown_filter() {
+        let text: &str = "if ({x || y} && {x !== y}) {x | foo}";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("x".to_string(), Variable::from("Foo"));
+
+        assert_eq!(text, resolve_variables(text, &vars));
+
+        let expected = Err("Unknown filter [foo]".to_string());
+        assert_eq!(expected, resolve_variables_strict(text, &vars));
+
+        let text: &str = "{name | Foo}";
+        let expected = Err("Unknown filter [Foo]".to_string());
+        assert_eq!(expected, resolve_variables_strict(text, &vars));
+    }
+
+    #[test]
+    fn test_resolve_variables_regex_count() {
+        let text: &str = r"^\d{3}-[a-z]{2,}$";
+
+        assert!(!has_variables(text));
+        assert_eq!(Ok(text.to_string()), resolve_variables_strict(text, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_resolve_variables_filter_error() {
+        let text: &str = "{price | number}";
+
+        let mut vars: HashMap<String, Variable> = HashMap::new();
+        vars.insert("price".to_string(), Variable::from("free"));
+
+        assert_eq!(text, resolve_variables(text, &vars));
+
+        let expected = Err("number: [free] is not a number".to_string());
+        assert_eq!(expected, resolve_variables_strict(text, &vars));
+    }
+
+    #[test]
+    fn test_resolve_variables_structured() {
+        let yaml = "
+        count: 3
+        user:
+          email: 'foo@bar.com'
+          tags:
+            - 'a'
+            - 'b'
+        ";
+
+        let mapping: Mapping = serde_yaml::from_str(yaml).unwrap();
+        let vars: HashMap<String, Variable> = to_variables(&mapping).unwrap().into_iter().collect();
+
+        let result = resolve_variables("{user.email} {user.tags.1} {count} {user.tags}", &vars);
+        let expected = "foo@bar.com b 3 [\"a\",\"b\"]".to_string();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn test_resolve_variable_typed() {
+        let mapping: Mapping = serde_yaml::from_str("count: 3").unwrap();
+        let vars: HashMap<String, Variable> = to_variables(&mapping).unwrap().into_iter().collect();
+
+        let result = resolve_variable("{count}", &vars);
+        assert_eq!(Ok(Variable::from(3.0)), result);
+
+        let result = resolve_variable("{count | trim}", &vars);
+        assert_eq!(Ok(Variable::from("3")), result);
+
+        let result = resolve_variable("{missing}", &vars);
+        assert_eq!(Err("Undefined variable: missing".to_string()), result);
+    }
 }
@@ -0,0 +1,173 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt::{Display, Write};
+
+const DATE_TIME_FORMATS: [&str; 3] = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M"];
+const DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%d/%m/%Y"];
+
+/// Transformation applied to a variable with `{name | filter}`, chained
+/// like `{price | trim | number}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Upper,
+    Lower,
+    Trim,
+    Number,
+    UrlEncode,
+    Replace(String, String),
+    Format(String),
+}
+
+impl Filter {
+    /// Parses `name` or `name:arg[:arg]`. An unknown name fails, the token
+    /// is then left as written, or fails in strict mode.
+    pub fn parse(text: &str) -> Result<Filter, String> {
+        let text = text.trim();
+        let (name, args) = match text.split_once(':') {
+            Some((name, args)) => (name, Some(args)),
+            None => (text, None),
+        };
+
+        match (name, args) {
+            ("upper", None) => Ok(Filter::Upper),
+            ("lower", None) => Ok(Filter::Lower),
+            ("trim", None) => Ok(Filter::Trim),
+            ("number", None) => Ok(Filter::Number),
+            ("urlencode", None) => Ok(Filter::UrlEncode),
+            ("replace", Some(args)) => match args.split_once(':') {
+                Some(("", _)) => Err("replace: pattern can`t be empty".to_string()),
+                Some((from, to)) => Ok(Filter::Replace(from.to_string(), to.to_string())),
+                None => Err("replace: expected replace:from:to".to_string()),
+            },
+            ("format", Some(format)) if !format.is_empty() => Ok(Filter::Format(format.to_string())),
+            ("format", _) => Err("format: expected format:pattern".to_string()),
+            _ => Err(format!("Unknown filter [{}]", text)),
+        }
+    }
+
+    pub fn apply(&self, value: &str) -> Result<String, String> {
+        match self {
+            Filter::Upper => Ok(value.to_uppercase()),
+            Filter::Lower => Ok(value.to_lowercase()),
+            Filter::Trim => Ok(value.trim().to_string()),
+            Filter::Number => to_number(value),
+            Filter::UrlEncode => Ok(url_encode(value)),
+            Filter::Replace(from, to) => Ok(value.replace(from, to)),
+            Filter::Format(format) => format_date(value, format),
+        }
+    }
+}
+
+/// Reads the one number in `value`, so `$ 1,234.50` becomes `1234.50`.
+/// Commas are only allowed as thousands separators and the decimals are
+/// kept as written.
+fn to_number(value: &str) -> Result<String, String> {
+    lazy_static! {
+        static ref RE_NUMBER: Regex =
+            Regex::new(r"-?(?:\d{1,3}(?:,\d{3})+|\d+)(?:\.\d+)?|-?\.\d+").unwrap();
+    }
+
+    let mut numbers = RE_NUMBER.find_iter(value);
+    match (numbers.next(), numbers.next()) {
+        (Some(number), None) => Ok(number.as_str().replace(',', "")),
+        (Some(_), Some(_)) => Err(format!("number: [{}] has more than one number", value)),
+        (None, _) => Err(format!("number: [{}] is not a number", value)),
+    }
+}
+
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn format_date(value: &str, format: &str) -> Result<String, String> {
+    let value = value.trim();
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return write_date(date.format(format), format);
+    }
+
+    for date_format in DATE_TIME_FORMATS {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, date_format) {
+            return write_date(date.format(format), format);
+        }
+    }
+
+    for date_format in DATE_FORMATS {
+        if let Ok(date) = NaiveDate::parse_from_str(value, date_format) {
+            return write_date(date.format(format), format);
+        }
+    }
+
+    Err(format!("format: [{}] is not a date", value))
+}
+
+// `to_string` panics on an invalid pattern, so write it out by hand.
+fn write_date(date: impl Display, format: &str) -> Result<String, String> {
+    let mut formatted = String::new();
+    match write!(formatted, "{}", date) {
+        Ok(_) => Ok(formatted),
+        Err(_) => Err(format!("format: [{}] is not a valid pattern", format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unknown() {
+        assert_eq!(Err("Unknown filter [Foo]".to_string()), Filter::parse("Foo"));
+        let expected = Err("Unknown filter [upper:foo]".to_string());
+        assert_eq!(expected, Filter::parse(" upper:foo"));
+    }
+
+    #[test]
+    fn test_parse_replace() {
+        let expected = Ok(Filter::Replace("a".to_string(), "b:c".to_string()));
+        assert_eq!(expected, Filter::parse(" replace:a:b:c "));
+
+        let expected = Err("replace: expected replace:from:to".to_string());
+        assert_eq!(expected, Filter::parse("replace:a"));
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(Ok("1234.50".to_string()), Filter::Number.apply("$ 1,234.50"));
+        assert_eq!(Ok("-3".to_string()), Filter::Number.apply("-3 items"));
+        assert_eq!(Ok("1299.00".to_string()), Filter::Number.apply("1299.00"));
+        assert!(Filter::Number.apply("none").is_err());
+
+        let expected = Err("number: [Page 2 of 10] has more than one number".to_string());
+        assert_eq!(expected, Filter::Number.apply("Page 2 of 10"));
+        assert!(Filter::Number.apply("Total 1,234 (5 pages)").is_err());
+        assert!(Filter::Number.apply("2024-01-05").is_err());
+    }
+
+    #[test]
+    fn test_url_encode() {
+        let result = Filter::UrlEncode.apply("rust lang&co/é");
+        assert_eq!(Ok("rust%20lang%26co%2F%C3%A9".to_string()), result);
+    }
+
+    #[test]
+    fn test_format() {
+        let filter = Filter::Format("%d/%m".to_string());
+        assert_eq!(Ok("24/12".to_string()), filter.apply("2023-12-24"));
+        assert_eq!(Ok("24/12".to_string()), filter.apply("2023-12-24T10:00:00+02:00"));
+        assert!(filter.apply("tomorrow").is_err());
+
+        let filter = Filter::Format("%Q".to_string());
+        let expected = Err("format: [%Q] is not a valid pattern".to_string());
+        assert_eq!(expected, filter.apply("2023-12-24"));
+    }
+}
@@ -0,0 +1,197 @@
+use serde::Serialize;
+use serde_yaml::{Number, Value};
+use std::{collections::BTreeMap, fmt};
+
+/// Value stored in `WebDriverSession.variables`, keeping the type it was
+/// declared with. It is only turned into text when substituted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Variable {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    List(Vec<Variable>),
+    Map(BTreeMap<String, Variable>),
+}
+
+// Numbers are compared like `serde_yaml::Value` does, NaN included.
+impl Eq for Variable {}
+
+impl Variable {
+    /// Walks a dotted path (`user.email`, `items.0`) into maps and lists.
+    pub fn get_path(&self, path: &[String]) -> Option<&Variable> {
+        let mut current = self;
+        for key in path {
+            current = match current {
+                Variable::Map(map) => map.get(key)?,
+                Variable::List(list) => list.get(key.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Variable::Number(n) => n.as_f64(),
+            Variable::String(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Variable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variable::Null => Ok(()),
+            Variable::Bool(b) => write!(f, "{}", b),
+            Variable::Number(n) => write!(f, "{}", n),
+            Variable::String(s) => write!(f, "{}", s),
+            Variable::List(_) | Variable::Map(_) => match serde_json::to_string(self) {
+                Ok(json) => write!(f, "{}", json),
+                Err(_) => Err(fmt::Error),
+            },
+        }
+    }
+}
+
+impl From<&str> for Variable {
+    fn from(value: &str) -> Self {
+        Variable::String(value.to_string())
+    }
+}
+
+impl From<String> for Variable {
+    fn from(value: String) -> Self {
+        Variable::String(value)
+    }
+}
+
+impl From<bool> for Variable {
+    fn from(value: bool) -> Self {
+        Variable::Bool(value)
+    }
+}
+
+impl From<f64> for Variable {
+    /// Whole numbers are stored as integers so they print as `3`, not `3.0`.
+    fn from(value: f64) -> Self {
+        if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+            return Variable::Number(Number::from(value as i64));
+        }
+        Variable::Number(Number::from(value))
+    }
+}
+
+impl TryFrom<&Value> for Variable {
+    type Error = String;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Null => Ok(Variable::Null),
+            Value::Bool(b) => Ok(Variable::Bool(*b)),
+            Value::Number(n) => Ok(Variable::Number(n.clone())),
+            Value::String(s) => Ok(Variable::String(s.clone())),
+            Value::Sequence(sequence) => Ok(Variable::List(
+                sequence
+                    .iter()
+                    .map(Variable::try_from)
+                    .collect::<Result<Vec<Variable>, String>>()?,
+            )),
+            Value::Mapping(mapping) => {
+                let mut map: BTreeMap<String, Variable> = BTreeMap::new();
+                for (key, value) in mapping {
+                    let key = match key.as_str() {
+                        None => return Err(format!("Key: {:?} is not a string", key)),
+                        Some(k) => k.to_owned(),
+                    };
+                    map.insert(key, Variable::try_from(value)?);
+                }
+                Ok(Variable::Map(map))
+            }
+            Value::Tagged(tagged) => Variable::try_from(&tagged.value),
+        }
+    }
+}
+
+impl From<serde_json::Value> for Variable {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Variable::Null,
+            serde_json::Value::Bool(b) => Variable::Bool(b),
+            serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+                (Some(i), _) => Variable::Number(Number::from(i)),
+                (None, Some(f)) => Variable::Number(Number::from(f)),
+                (None, None) => Variable::String(n.to_string()),
+            },
+            serde_json::Value::String(s) => Variable::String(s),
+            serde_json::Value::Array(list) => {
+                Variable::List(list.into_iter().map(Variable::from).collect())
+            }
+            serde_json::Value::Object(map) => Variable::Map(
+                map.into_iter()
+                    .map(|(key, value)| (key, Variable::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_value() {
+        let yaml = "
+        count: 3
+        price: 2.5
+        active: true
+        tags: ['a', 'b']
+        ";
+
+        let value: Value = serde_yaml::from_str(yaml).unwrap();
+        let result = Variable::try_from(&value).unwrap();
+
+        let mut expected: BTreeMap<String, Variable> = BTreeMap::new();
+        expected.insert("count".to_string(), Variable::Number(Number::from(3)));
+        expected.insert("price".to_string(), Variable::Number(Number::from(2.5)));
+        expected.insert("active".to_string(), Variable::Bool(true));
+        expected.insert(
+            "tags".to_string(),
+            Variable::List(vec![Variable::from("a"), Variable::from("b")]),
+        );
+
+        assert_eq!(Variable::Map(expected), result)
+    }
+
+    #[test]
+    fn test_try_from_value_key_not_str() {
+        let value: Value = serde_yaml::from_str("2: 'foo'").unwrap();
+        let result = Variable::try_from(&value);
+        assert_eq!(Err("Key: Number(2) is not a string".to_string()), result)
+    }
+
+    #[test]
+    fn test_get_path() {
+        let value: Value = serde_yaml::from_str("user: { emails: ['a@b.c'] }").unwrap();
+        let variable = Variable::try_from(&value).unwrap();
+
+        let path: Vec<String> = vec!["user".into(), "emails".into(), "0".into()];
+        assert_eq!(Some(&Variable::from("a@b.c")), variable.get_path(&path));
+
+        let path: Vec<String> = vec!["user".into(), "name".into()];
+        assert_eq!(None, variable.get_path(&path));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("3", Variable::from(3.0).to_string());
+        assert_eq!("2.5", Variable::from(2.5).to_string());
+        assert_eq!("", Variable::Null.to_string());
+
+        let list = Variable::List(vec![Variable::from("a"), Variable::from(1.0)]);
+        assert_eq!("[\"a\",1]", list.to_string());
+    }
+}
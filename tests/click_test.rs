@@ -239,7 +239,7 @@ mod click {
 
         assert_eq!(result.len(), 5);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -293,7 +293,7 @@ mod click {
 
         assert_eq!(result.len(), 4);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -79,7 +79,7 @@ mod close {
 
         assert_eq!(result.len(), 2);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -110,7 +110,7 @@ mod close {
 
         assert_eq!(result.len(), 2);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -141,7 +141,7 @@ mod close {
 
         assert_eq!(result.len(), 2);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -56,9 +56,10 @@ pub fn setup(file_name: &str, data: &str) {
     let f = std::fs::OpenOptions::new()
         .write(true)
         .create(true)
+        .truncate(true)
         .open(path)
         .expect("Couldn't open file");
-    let data: TaskData = match serde_yaml::from_str::<TaskData>(&data) {
+    let data: TaskData = match serde_yaml::from_str::<TaskData>(data) {
         Ok(data) => data,
         Err(err) => {
             cleanup(file_name);
@@ -67,24 +68,24 @@ pub fn setup(file_name: &str, data: &str) {
     };
     
     match serde_yaml::to_writer(f, &data) {
-        Ok(_) => return,
+        Ok(_) => (),
         Err(err) => {
             cleanup(file_name);
             panic!("{}", err)
         }
-    };
+    }
 }
 
 pub fn cleanup(file_name: &str) {
     let mut path = resource_path_tmp();
     path.push(format!("{file_name}.yml"));
-    fs::remove_file(&path).expect(&format!("clean_up: unable to remove file: {:#?}", &path));
+    fs::remove_file(&path).unwrap_or_else(|_| panic!("clean_up: unable to remove file: {:#?}", &path));
 }
 
 #[allow(dead_code)]
 pub fn validate_first_result(task: &TaskOk, message: &str) {
     let results = task.result.clone().unwrap();
-    let first_result = results.get(0).unwrap();
+    let first_result = results.first().unwrap();
     assert_eq!(ValidationReultType::SUCCESS, first_result.validation);
     assert_eq!(message, first_result.message);
 }
@@ -85,7 +85,7 @@ mod integration_test {
 
         assert_eq!(result.len(), 13);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -32,7 +32,7 @@ mod link {
 
         assert_eq!(result.len(), 3);
 
-        let link_task = result.get(0).unwrap();
+        let link_task = result.first().unwrap();
         assert_eq!(link_task.name, "Open wikipedia");
         assert_eq!(link_task.task_type, TaskTypes::LINK);
         assert_eq!(link_task.result, None);
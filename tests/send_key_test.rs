@@ -195,7 +195,7 @@ mod send_key {
 
         assert_eq!(result.len(), 4);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -245,7 +245,7 @@ mod send_key {
 
         assert_eq!(result.len(), 5);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);
@@ -52,7 +52,7 @@ mod wait {
 
         let result = executor_ok!(data);
 
-        let task = result.get(0).unwrap();
+        let task = result.first().unwrap();
         assert_eq!(task.name, "Open wikipedia");
         assert_eq!(task.task_type, TaskTypes::LINK);
         assert_eq!(task.result, None);